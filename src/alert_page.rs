@@ -0,0 +1,79 @@
+use crate::icon;
+use crate::page::{arrange, clock_row, fit, Page, PageContext, TIME_FORMAT};
+use crate::weather::{Alert, Forecast};
+use crate::Text;
+use time::OffsetDateTime;
+
+/// Take as many of `words` as fit in `width` cells, at least one which is cut off if it's too long
+fn fill<'a>(
+    words: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+    width: usize,
+) -> String {
+    let mut row = String::new();
+    while let Some(word) = words.peek() {
+        let len = icon::width(&row) + usize::from(!row.is_empty()) + icon::width(word);
+        if len > width && !row.is_empty() {
+            break;
+        }
+        if !row.is_empty() {
+            row.push(' ');
+        }
+        row.push_str(word);
+        words.next();
+    }
+    icon::truncate(&row, width).to_string()
+}
+
+/// Split `s` into two rows of `width` cells at word boundaries, the words which don't fit are left
+/// out
+fn wrap(s: &str, width: usize) -> [String; 2] {
+    let mut words = s.split_whitespace().peekable();
+    let first = fill(&mut words, width);
+    let second = fill(&mut words, width);
+    [first, second]
+}
+
+/// Alerts in effect at unix timestamp `now`
+pub fn active_alerts(forecast: &Forecast, now: i64) -> impl Iterator<Item = &Alert> {
+    forecast
+        .alerts
+        .iter()
+        .filter(move |alert| alert.start <= now && now < alert.end)
+}
+
+/// The first of the weather alerts in effect, skipped if there are none
+#[derive(Debug)]
+pub struct AlertPage;
+
+impl Page for AlertPage {
+    fn render(&self, ctx: &PageContext) -> Option<Text> {
+        let mut active = active_alerts(ctx.forecast, ctx.now.unix_timestamp());
+        let alert = active.next()?;
+        let others = active.count();
+
+        let end = OffsetDateTime::from_unix_timestamp(alert.end).ok()?;
+        let end = ctx
+            .settings
+            .timezone
+            .to_local(end)
+            .format(&TIME_FORMAT)
+            .ok()?;
+        let others = match others {
+            0 => String::new(),
+            others => format!(" +{others}"),
+        };
+        let phrases = ctx.settings.locale.phrases();
+        let (alert_label, until) = (phrases.alert, phrases.until);
+        let header = fit(
+            ctx,
+            &[
+                format!("{alert_label} {until} {end}{others}"),
+                format!("{until} {end}{others}"),
+                format!("{end}{others}"),
+            ],
+        );
+        let [first, second] = wrap(&alert.event, ctx.row_width());
+
+        Some(arrange(ctx, &[clock_row(ctx), header, first, second]))
+    }
+}
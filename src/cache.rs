@@ -0,0 +1,42 @@
+use crate::weather::Forecast;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Last successful forecast together with the time it was fetched at
+#[derive(Serialize, Deserialize)]
+pub struct CachedForecast {
+    /// Unix timestamp of the fetch
+    pub fetched_at: i64,
+    pub forecast: Forecast,
+}
+
+/// On-disk copy of the last successful forecast, so that it can be shown right after a restart
+#[derive(Clone, Debug)]
+pub struct ForecastCache {
+    path: PathBuf,
+}
+
+impl ForecastCache {
+    pub fn in_dir(state_dir: &Path) -> Self {
+        Self {
+            path: state_dir.join("forecast.json"),
+        }
+    }
+
+    pub fn load(&self) -> anyhow::Result<CachedForecast> {
+        let file = std::fs::File::open(&self.path)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+
+    pub fn store(&self, cached: &CachedForecast) -> anyhow::Result<()> {
+        // write and sync a temporary file first, so that a power cut can't leave a truncated
+        // cache behind
+        let tmp = self.path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec(cached)?)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
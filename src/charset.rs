@@ -0,0 +1,295 @@
+/// Characters of the rows 0xE0 and 0xF0 of the A00 character ROM
+const A00_HIGH: [char; 32] = [
+    'α', 'ä', 'β', 'ε', 'μ', 'σ', 'ρ', 'g', '√', '⁻', 'j', 'ˣ', '¢', '£', 'ñ', 'ö', //
+    'p', 'q', 'θ', '∞', 'Ω', 'ü', 'Σ', 'π', 'x', 'y', '千', '万', '円', '÷', ' ', '█',
+];
+
+/// Unicode character resembling the character `code` of the A00 (Japanese) character ROM of the
+/// HD44780. The CGRAM codes 0x00 to 0x0F have no fixed shape and are shown as spaces, like the
+/// codes with no character in the ROM.
+pub fn a00_char(code: u8) -> char {
+    match code {
+        0x5C => '¥',
+        0x7E => '→',
+        0x7F => '←',
+        0x20..=0x7D => code as char,
+        // the handakuten, commonly used as the degree sign
+        0xDF => '°',
+        // half-width katakana and punctuation, in the same order as in Unicode
+        0xA1..=0xDE => char::from_u32(0xFF61 + u32::from(code - 0xA1)).unwrap(),
+        0xE0..=0xFF => A00_HIGH[usize::from(code - 0xE0)],
+        _ => ' ',
+    }
+}
+
+/// Code of the A00 character ROM showing `c` as it is, if there is one
+pub fn a00_code(c: char) -> Option<u8> {
+    match c {
+        // the ROM has the yen sign and an arrow in place of the backslash and the tilde
+        '\\' | '~' => None,
+        ' '..='}' => Some(c as u8),
+        _ => [0x5C, 0x7E, 0x7F]
+            .into_iter()
+            .chain(0xA1..=0xFF)
+            .find(|&code| a00_char(code) == c),
+    }
+}
+
+/// Diacritic drawn above a letter in the two top rows of a CGRAM character
+#[derive(Clone, Copy)]
+enum Accent {
+    Acute,
+    Grave,
+    Caron,
+    Circumflex,
+    Ring,
+}
+
+impl Accent {
+    fn rows(self) -> [u8; 2] {
+        match self {
+            Accent::Acute => [0b00010, 0b00100],
+            Accent::Grave => [0b01000, 0b00100],
+            Accent::Caron => [0b01010, 0b00100],
+            Accent::Circumflex => [0b00100, 0b01010],
+            Accent::Ring => [0b01110, 0b01010],
+        }
+    }
+}
+
+/// Rows 2 to 6 of the lowercase letters which are given accents, in the style of the ROM font
+fn base_rows(base: char) -> [u8; 5] {
+    match base {
+        'a' => [0b01110, 0b00001, 0b01111, 0b10001, 0b01111],
+        'c' => [0b01110, 0b10000, 0b10000, 0b10001, 0b01110],
+        'e' => [0b01110, 0b10001, 0b11111, 0b10000, 0b01110],
+        // dotless, the accent takes the place of the dot
+        'i' => [0b01100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'n' => [0b10110, 0b11001, 0b10001, 0b10001, 0b10001],
+        'o' => [0b01110, 0b10001, 0b10001, 0b10001, 0b01110],
+        'r' => [0b10110, 0b11001, 0b10000, 0b10000, 0b10000],
+        's' => [0b01110, 0b10000, 0b01110, 0b00001, 0b11110],
+        'u' => [0b10001, 0b10001, 0b10001, 0b10011, 0b01101],
+        'y' => [0b10001, 0b10001, 0b01111, 0b00001, 0b01110],
+        'z' => [0b11111, 0b00010, 0b00100, 0b01000, 0b11111],
+        _ => unreachable!("no base letter {base}"),
+    }
+}
+
+/// Lowercase letters missing from the ROM which are drawn in CGRAM when there's a free slot
+pub const SYNTHESIZED: [char; 27] = [
+    'á', 'à', 'â', 'č', 'ç', 'é', 'è', 'ê', 'ě', 'í', 'î', 'ň', 'ó', 'ò', 'ô', 'ř', 'š', 'ú', 'ù',
+    'û', 'ů', 'ý', 'ž', 'ś', 'ź', 'ć', 'ń',
+];
+
+/// Base letter and accent of a synthesized letter
+fn decompose(c: char) -> Option<(char, Option<Accent>)> {
+    use Accent::*;
+    let decomposed = match c {
+        'á' => ('a', Some(Acute)),
+        'à' => ('a', Some(Grave)),
+        'â' => ('a', Some(Circumflex)),
+        'č' => ('c', Some(Caron)),
+        'ć' => ('c', Some(Acute)),
+        // the cedilla goes below the letter
+        'ç' => ('c', None),
+        'é' => ('e', Some(Acute)),
+        'è' => ('e', Some(Grave)),
+        'ê' => ('e', Some(Circumflex)),
+        'ě' => ('e', Some(Caron)),
+        'í' => ('i', Some(Acute)),
+        'î' => ('i', Some(Circumflex)),
+        'ň' => ('n', Some(Caron)),
+        'ń' => ('n', Some(Acute)),
+        'ó' => ('o', Some(Acute)),
+        'ò' => ('o', Some(Grave)),
+        'ô' => ('o', Some(Circumflex)),
+        'ř' => ('r', Some(Caron)),
+        'š' => ('s', Some(Caron)),
+        'ś' => ('s', Some(Acute)),
+        'ú' => ('u', Some(Acute)),
+        'ù' => ('u', Some(Grave)),
+        'û' => ('u', Some(Circumflex)),
+        'ů' => ('u', Some(Ring)),
+        'ý' => ('y', Some(Acute)),
+        'ž' => ('z', Some(Caron)),
+        'ź' => ('z', Some(Acute)),
+        _ => return None,
+    };
+    Some(decomposed)
+}
+
+/// Rows of the CGRAM character drawing `c`, if it's one of the synthesized letters
+pub fn synthesized_bitmap(c: char) -> Option<[u8; 8]> {
+    let (base, accent) = decompose(c)?;
+    let mut bitmap = [0; 8];
+    if let Some(accent) = accent {
+        bitmap[..2].copy_from_slice(&accent.rows());
+    }
+    bitmap[2..7].copy_from_slice(&base_rows(base));
+    if c == 'ç' {
+        bitmap[7] = 0b00100;
+    }
+    Some(bitmap)
+}
+
+/// ASCII approximation of `c`, used when it's neither in the ROM nor in CGRAM
+fn approximate(c: char) -> Option<&'static str> {
+    let approximation = match c {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Č' => "C",
+        'ç' | 'ć' | 'č' => "c",
+        'Ď' | 'Đ' => "D",
+        'ď' | 'đ' => "d",
+        'È'..='Ë' | 'Ē' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è'..='ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'Ì'..='Ï' | 'Ī' | 'İ' => "I",
+        'ì'..='ï' | 'ī' | 'ı' => "i",
+        'Ĺ' | 'Ľ' | 'Ł' => "L",
+        'ĺ' | 'ľ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ň' => "n",
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ő' => "O",
+        'ò'..='ö' | 'ø' | 'ō' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ř' => "R",
+        'ŕ' | 'ř' => "r",
+        'Ś' | 'Ş' | 'Š' => "S",
+        'ś' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' => "T",
+        'ţ' | 'ť' => "t",
+        'Ù'..='Ü' | 'Ū' | 'Ů' | 'Ű' => "U",
+        'ù'..='ü' | 'ū' | 'ů' | 'ű' => "u",
+        'Ý' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '\\' => "/",
+        '~' => "-",
+        '‐'..='―' | '−' => "-",
+        '‘' | '’' | '‚' => "'",
+        '“' | '”' | '„' | '«' | '»' => "\"",
+        '…' => "...",
+        '\u{a0}' => " ",
+        _ => return None,
+    };
+    Some(approximation)
+}
+
+/// Number of display cells `c` takes when it isn't an icon
+pub fn cells(c: char) -> usize {
+    if a00_code(c).is_some() || decompose(c).is_some() {
+        1
+    } else {
+        approximate(c).map_or(1, str::len)
+    }
+}
+
+/// Append the ROM codes showing `c` to `out`: the character itself if it's in the ROM, otherwise
+/// an ASCII approximation or a question mark. Synthesized letters without a CGRAM slot end up
+/// here too.
+pub fn transliterate(c: char, out: &mut Vec<u8>) {
+    match (a00_code(c), approximate(c)) {
+        (Some(code), _) => out.push(code),
+        (None, Some(approximation)) => out.extend_from_slice(approximation.as_bytes()),
+        (None, None) => out.push(b'?'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::icon::{Glyph, Icon, IconSet};
+
+    fn transliterated(c: char) -> Vec<u8> {
+        let mut out = Vec::new();
+        transliterate(c, &mut out);
+        out
+    }
+
+    #[test]
+    fn rom_round_trip() {
+        for code in 0x20..=0xFF {
+            let c = a00_char(code);
+            let back = a00_code(c).unwrap_or_else(|| panic!("no code of {c:?} from {code:#x}"));
+            assert_eq!(a00_char(back), c, "{code:#x}");
+        }
+    }
+
+    #[test]
+    fn rom_codes() {
+        assert_eq!(a00_code('A'), Some(0x41));
+        assert_eq!(a00_code('}'), Some(0x7D));
+        assert_eq!(a00_code('°'), Some(0xDF));
+        assert_eq!(a00_code('ä'), Some(0xE1));
+        assert_eq!(a00_code('ñ'), Some(0xEE));
+        assert_eq!(a00_code('¥'), Some(0x5C));
+        assert_eq!(a00_code('\\'), None);
+        assert_eq!(a00_code('~'), None);
+        assert_eq!(a00_code('ß'), None);
+    }
+
+    #[test]
+    fn approximations() {
+        assert_eq!(transliterated('ß'), b"ss");
+        assert_eq!(transliterated('\\'), b"/");
+        assert_eq!(transliterated('~'), b"-");
+        assert_eq!(transliterated('ľ'), b"l");
+        assert_eq!(transliterated('é'), b"e");
+        assert_eq!(transliterated('…'), b"...");
+        assert_eq!(transliterated('°'), [0xDF]);
+        assert_eq!(transliterated('€'), b"?");
+    }
+
+    #[test]
+    fn cells_of_approximations() {
+        assert_eq!(cells('a'), 1);
+        assert_eq!(cells('š'), 1);
+        assert_eq!(cells('ß'), 2);
+        assert_eq!(cells('…'), 3);
+        assert_eq!(cells('€'), 1);
+    }
+
+    #[test]
+    fn synthesized_bitmaps_are_distinct() {
+        for c in SYNTHESIZED {
+            let bitmap = synthesized_bitmap(c).unwrap();
+            assert_eq!(Glyph::from_bitmap(bitmap), Some(Glyph::Letter(c)), "{c}");
+        }
+        assert_eq!(synthesized_bitmap('a'), None);
+    }
+
+    #[test]
+    fn synthesized_letter_in_a_free_slot() {
+        let icons = IconSet::for_rows(["čaj"]);
+        assert_eq!(icons.glyphs(), [Glyph::Letter('č')]);
+        assert_eq!(icons.encode("čaj"), [0x00, b'a', b'j']);
+    }
+
+    #[test]
+    fn approximated_letter_without_a_free_slot() {
+        // icons take precedence over letters
+        let row = [
+            Icon::Thunder,
+            Icon::Wind,
+            Icon::Rain,
+            Icon::Sleet,
+            Icon::Snow,
+            Icon::Fog,
+            Icon::Cloud,
+            Icon::Sun,
+        ]
+        .into_iter()
+        .map(Icon::to_char)
+        .chain("čaj".chars())
+        .collect::<String>();
+        let icons = IconSet::for_rows([row.as_str()]);
+        assert!(!icons.glyphs().contains(&Glyph::Letter('č')));
+        assert_eq!(icons.encode("čaj"), b"caj");
+    }
+}
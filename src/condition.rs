@@ -0,0 +1,475 @@
+use serde::{Deserialize, Serialize};
+
+/// How strong a phenomenon is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Intensity {
+    Light,
+    Moderate,
+    Heavy,
+    Extreme,
+}
+
+/// What reduces the visibility
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Obscuration {
+    Mist,
+    Haze,
+    Smoke,
+    Dust,
+    Sand,
+    Fog,
+    VolcanicAsh,
+}
+
+/// How much of the sky is covered by clouds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Cloudiness {
+    /// No clouds
+    Clear,
+    /// 11-25 %
+    Few,
+    /// 25-50 %
+    Scattered,
+    /// 51-84 %
+    Broken,
+    /// 85-100 %
+    Overcast,
+}
+
+/// Dangerous wind
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Hazard {
+    Squalls,
+    Tornado,
+}
+
+/// Weather conditions at a point in time or over a period. Every phenomenon is missing unless it
+/// was reported.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Conditions {
+    pub thunder: Option<Intensity>,
+    pub drizzle: Option<Intensity>,
+    pub rain: Option<Intensity>,
+    /// The rain or drizzle freezes on contact
+    pub freezing: bool,
+    pub snow: Option<Intensity>,
+    /// The precipitation comes in showers
+    pub showers: bool,
+    pub obscuration: Option<Obscuration>,
+    pub clouds: Option<Cloudiness>,
+    pub hazard: Option<Hazard>,
+    /// The sun is below the horizon
+    pub night: bool,
+}
+
+impl Conditions {
+    /// Rain of any kind, including drizzle
+    pub fn any_rain(&self) -> Option<Intensity> {
+        self.rain.max(self.drizzle)
+    }
+
+    /// Conditions of a period covering both `self` and `other`, keeping the stronger phenomena
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            thunder: self.thunder.max(other.thunder),
+            drizzle: self.drizzle.max(other.drizzle),
+            rain: self.rain.max(other.rain),
+            freezing: self.freezing || other.freezing,
+            snow: self.snow.max(other.snow),
+            showers: self.showers || other.showers,
+            obscuration: self.obscuration.max(other.obscuration),
+            clouds: self.clouds.max(other.clouds),
+            hazard: self.hazard.max(other.hazard),
+            night: self.night && other.night,
+        }
+    }
+
+    /// Conditions of an OpenWeatherMap condition code, see
+    /// <https://openweathermap.org/weather-conditions>. Unknown codes have no conditions.
+    pub fn from_owm_code(code: i64) -> Self {
+        use Intensity::*;
+
+        let none = Self::default();
+        match code {
+            // thunderstorm
+            200 => Self {
+                thunder: Some(Moderate),
+                rain: Some(Light),
+                ..none
+            },
+            201 => Self {
+                thunder: Some(Moderate),
+                rain: Some(Moderate),
+                ..none
+            },
+            202 => Self {
+                thunder: Some(Moderate),
+                rain: Some(Heavy),
+                ..none
+            },
+            210 => Self {
+                thunder: Some(Light),
+                ..none
+            },
+            211 | 221 => Self {
+                thunder: Some(Moderate),
+                ..none
+            },
+            212 => Self {
+                thunder: Some(Heavy),
+                ..none
+            },
+            230 => Self {
+                thunder: Some(Moderate),
+                drizzle: Some(Light),
+                ..none
+            },
+            231 => Self {
+                thunder: Some(Moderate),
+                drizzle: Some(Moderate),
+                ..none
+            },
+            232 => Self {
+                thunder: Some(Moderate),
+                drizzle: Some(Heavy),
+                ..none
+            },
+
+            // drizzle
+            300 => Self {
+                drizzle: Some(Light),
+                ..none
+            },
+            301 => Self {
+                drizzle: Some(Moderate),
+                ..none
+            },
+            302 => Self {
+                drizzle: Some(Heavy),
+                ..none
+            },
+            310 => Self {
+                drizzle: Some(Light),
+                rain: Some(Light),
+                ..none
+            },
+            311 => Self {
+                drizzle: Some(Moderate),
+                rain: Some(Moderate),
+                ..none
+            },
+            312 => Self {
+                drizzle: Some(Heavy),
+                rain: Some(Heavy),
+                ..none
+            },
+            313 => Self {
+                drizzle: Some(Moderate),
+                rain: Some(Moderate),
+                showers: true,
+                ..none
+            },
+            314 => Self {
+                drizzle: Some(Heavy),
+                rain: Some(Heavy),
+                showers: true,
+                ..none
+            },
+            321 => Self {
+                drizzle: Some(Moderate),
+                showers: true,
+                ..none
+            },
+
+            // rain
+            500 => Self {
+                rain: Some(Light),
+                ..none
+            },
+            501 => Self {
+                rain: Some(Moderate),
+                ..none
+            },
+            502 | 503 => Self {
+                rain: Some(Heavy),
+                ..none
+            },
+            504 => Self {
+                rain: Some(Extreme),
+                ..none
+            },
+            511 => Self {
+                rain: Some(Moderate),
+                freezing: true,
+                ..none
+            },
+            520 => Self {
+                rain: Some(Light),
+                showers: true,
+                ..none
+            },
+            521 | 531 => Self {
+                rain: Some(Moderate),
+                showers: true,
+                ..none
+            },
+            522 => Self {
+                rain: Some(Heavy),
+                showers: true,
+                ..none
+            },
+
+            // snow
+            600 => Self {
+                snow: Some(Light),
+                ..none
+            },
+            601 => Self {
+                snow: Some(Moderate),
+                ..none
+            },
+            602 => Self {
+                snow: Some(Heavy),
+                ..none
+            },
+            611 | 616 => Self {
+                rain: Some(Moderate),
+                snow: Some(Moderate),
+                ..none
+            },
+            612 => Self {
+                rain: Some(Light),
+                snow: Some(Light),
+                showers: true,
+                ..none
+            },
+            613 => Self {
+                rain: Some(Moderate),
+                snow: Some(Moderate),
+                showers: true,
+                ..none
+            },
+            615 => Self {
+                rain: Some(Light),
+                snow: Some(Light),
+                ..none
+            },
+            620 => Self {
+                snow: Some(Light),
+                showers: true,
+                ..none
+            },
+            621 => Self {
+                snow: Some(Moderate),
+                showers: true,
+                ..none
+            },
+            622 => Self {
+                snow: Some(Heavy),
+                showers: true,
+                ..none
+            },
+
+            // atmosphere
+            701 => Self::obscured(Obscuration::Mist),
+            711 => Self::obscured(Obscuration::Smoke),
+            721 => Self::obscured(Obscuration::Haze),
+            731 | 761 => Self::obscured(Obscuration::Dust),
+            741 => Self::obscured(Obscuration::Fog),
+            751 => Self::obscured(Obscuration::Sand),
+            762 => Self::obscured(Obscuration::VolcanicAsh),
+            771 => Self {
+                hazard: Some(Hazard::Squalls),
+                ..none
+            },
+            781 => Self {
+                hazard: Some(Hazard::Tornado),
+                ..none
+            },
+
+            // clear and clouds
+            800 => Self::clouded(Cloudiness::Clear),
+            801 => Self::clouded(Cloudiness::Few),
+            802 => Self::clouded(Cloudiness::Scattered),
+            803 => Self::clouded(Cloudiness::Broken),
+            804 => Self::clouded(Cloudiness::Overcast),
+
+            _ => none,
+        }
+    }
+
+    pub fn obscured(obscuration: Obscuration) -> Self {
+        Self {
+            obscuration: Some(obscuration),
+            ..Default::default()
+        }
+    }
+
+    pub fn clouded(clouds: Cloudiness) -> Self {
+        Self {
+            clouds: Some(clouds),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a crate::owm::Weather>> From<I> for Conditions {
+    fn from(i: I) -> Self {
+        let mut night = false;
+        let conditions = i
+            .inspect(|w| night |= w.icon.ends_with('n'))
+            .map(|w| Conditions::from_owm_code(w.id))
+            .fold(Conditions::default(), Conditions::merge);
+        Conditions {
+            night,
+            ..conditions
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Intensity::*;
+
+    /// Code with its thunder, drizzle, rain and snow, and whether they come in showers
+    type Precipitation = (i64, I, I, I, I, bool);
+    type I = Option<Intensity>;
+
+    #[rustfmt::skip]
+    const PRECIPITATION: &[Precipitation] = &[
+        (200, Some(Moderate), None, Some(Light), None, false),
+        (201, Some(Moderate), None, Some(Moderate), None, false),
+        (202, Some(Moderate), None, Some(Heavy), None, false),
+        (210, Some(Light), None, None, None, false),
+        (211, Some(Moderate), None, None, None, false),
+        (212, Some(Heavy), None, None, None, false),
+        (221, Some(Moderate), None, None, None, false),
+        (230, Some(Moderate), Some(Light), None, None, false),
+        (231, Some(Moderate), Some(Moderate), None, None, false),
+        (232, Some(Moderate), Some(Heavy), None, None, false),
+        (300, None, Some(Light), None, None, false),
+        (301, None, Some(Moderate), None, None, false),
+        (302, None, Some(Heavy), None, None, false),
+        (310, None, Some(Light), Some(Light), None, false),
+        (311, None, Some(Moderate), Some(Moderate), None, false),
+        (312, None, Some(Heavy), Some(Heavy), None, false),
+        (313, None, Some(Moderate), Some(Moderate), None, true),
+        (314, None, Some(Heavy), Some(Heavy), None, true),
+        (321, None, Some(Moderate), None, None, true),
+        (500, None, None, Some(Light), None, false),
+        (501, None, None, Some(Moderate), None, false),
+        (502, None, None, Some(Heavy), None, false),
+        (503, None, None, Some(Heavy), None, false),
+        (504, None, None, Some(Extreme), None, false),
+        (511, None, None, Some(Moderate), None, false),
+        (520, None, None, Some(Light), None, true),
+        (521, None, None, Some(Moderate), None, true),
+        (522, None, None, Some(Heavy), None, true),
+        (531, None, None, Some(Moderate), None, true),
+        (600, None, None, None, Some(Light), false),
+        (601, None, None, None, Some(Moderate), false),
+        (602, None, None, None, Some(Heavy), false),
+        (611, None, None, Some(Moderate), Some(Moderate), false),
+        (612, None, None, Some(Light), Some(Light), true),
+        (613, None, None, Some(Moderate), Some(Moderate), true),
+        (615, None, None, Some(Light), Some(Light), false),
+        (616, None, None, Some(Moderate), Some(Moderate), false),
+        (620, None, None, None, Some(Light), true),
+        (621, None, None, None, Some(Moderate), true),
+        (622, None, None, None, Some(Heavy), true),
+    ];
+
+    const OBSCURATION: &[(i64, Obscuration)] = &[
+        (701, Obscuration::Mist),
+        (711, Obscuration::Smoke),
+        (721, Obscuration::Haze),
+        (731, Obscuration::Dust),
+        (741, Obscuration::Fog),
+        (751, Obscuration::Sand),
+        (761, Obscuration::Dust),
+        (762, Obscuration::VolcanicAsh),
+    ];
+
+    const HAZARD: &[(i64, Hazard)] = &[(771, Hazard::Squalls), (781, Hazard::Tornado)];
+
+    const CLOUDS: &[(i64, Cloudiness)] = &[
+        (800, Cloudiness::Clear),
+        (801, Cloudiness::Few),
+        (802, Cloudiness::Scattered),
+        (803, Cloudiness::Broken),
+        (804, Cloudiness::Overcast),
+    ];
+
+    /// Every documented code with its conditions
+    fn documented() -> Vec<(i64, Conditions)> {
+        let precipitation =
+            PRECIPITATION
+                .iter()
+                .map(|&(code, thunder, drizzle, rain, snow, showers)| {
+                    let conditions = Conditions {
+                        thunder,
+                        drizzle,
+                        rain,
+                        freezing: code == 511,
+                        snow,
+                        showers,
+                        ..Default::default()
+                    };
+                    (code, conditions)
+                });
+        let obscuration = OBSCURATION
+            .iter()
+            .map(|&(code, obscuration)| (code, Conditions::obscured(obscuration)));
+        let hazard = HAZARD.iter().map(|&(code, hazard)| {
+            let conditions = Conditions {
+                hazard: Some(hazard),
+                ..Default::default()
+            };
+            (code, conditions)
+        });
+        let clouds = CLOUDS
+            .iter()
+            .map(|&(code, clouds)| (code, Conditions::clouded(clouds)));
+        precipitation
+            .chain(obscuration)
+            .chain(hazard)
+            .chain(clouds)
+            .collect()
+    }
+
+    #[test]
+    fn documented_codes() {
+        for (code, expected) in documented() {
+            assert_eq!(Conditions::from_owm_code(code), expected, "code {code}");
+        }
+    }
+
+    #[test]
+    fn undocumented_codes_have_no_conditions() {
+        let documented = documented();
+        for code in 200..=804 {
+            if documented.iter().all(|&(c, _)| c != code) {
+                assert_eq!(
+                    Conditions::from_owm_code(code),
+                    Conditions::default(),
+                    "code {code}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn all_reported_conditions_are_merged() {
+        let weather = [500, 211, 600].map(|id| crate::owm::Weather {
+            id,
+            icon: "10d".to_string(),
+            ..Default::default()
+        });
+        let conditions = Conditions::from(weather.iter());
+        assert_eq!(conditions.rain, Some(Light));
+        assert_eq!(conditions.thunder, Some(Moderate));
+        assert_eq!(conditions.snow, Some(Light));
+        assert!(!conditions.night);
+    }
+}
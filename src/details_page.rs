@@ -0,0 +1,151 @@
+use crate::page::{arrange, clock_row, display_temperature, fit, unit_symbol, Page, PageContext};
+use crate::temperature::Units;
+use crate::weather::Details;
+use crate::wind::{beaufort, compass_point};
+use crate::Text;
+
+/// Text of a missing value
+const MISSING: &str = "-";
+
+/// Wind speed given in m/s, converted to the speed unit matching `units` or to the force on the
+/// Beaufort scale
+fn wind_value(speed: f32, units: Units, use_beaufort: bool) -> u32 {
+    match units {
+        _ if use_beaufort => u32::from(beaufort(speed)),
+        Units::Imperial => (speed * 2.23694).round() as u32,
+        _ => speed.round() as u32,
+    }
+}
+
+fn wind_unit(units: Units, use_beaufort: bool) -> &'static str {
+    match units {
+        _ if use_beaufort => "Bft",
+        Units::Imperial => "mph",
+        _ => "m/s",
+    }
+}
+
+/// Pressure given in hPa, in inches of mercury for imperial units
+fn display_pressure(pressure: Option<f32>, units: Units) -> String {
+    match (pressure, units) {
+        (None, _) => MISSING.to_string(),
+        (Some(pressure), Units::Imperial) => format!("{:.2}inHg", pressure * 0.02953),
+        (Some(pressure), _) => format!("{:.0}hPa", pressure),
+    }
+}
+
+/// Visibility given in meters, in miles for imperial units. Short distances, which matter in fog,
+/// are shown more precisely.
+fn display_visibility(visibility: Option<f32>, units: Units) -> String {
+    let Some(visibility) = visibility else {
+        return MISSING.to_string();
+    };
+    let (distance, unit) = match units {
+        Units::Imperial => (visibility / 1609.34, "mi"),
+        _ if visibility < 1000.0 => return format!("{visibility:.0}m"),
+        _ => (visibility / 1000.0, "km"),
+    };
+    match distance < 10.0 {
+        true => format!("{distance:.1}{unit}"),
+        false => format!("{distance:.0}{unit}"),
+    }
+}
+
+fn display_percent(value: Option<f32>) -> String {
+    value.map_or(MISSING.to_string(), |value| format!("{value:.0}%"))
+}
+
+/// Wind, humidity, pressure and the other details of the current weather. The last row alternates
+/// between the pressure with the UV index and the cloud cover with the visibility. Skipped if the
+/// provider reports none of them.
+#[derive(Debug)]
+pub struct DetailsPage;
+
+impl Page for DetailsPage {
+    fn render(&self, ctx: &PageContext) -> Option<Text> {
+        let details = ctx.forecast.current_at(ctx.now.unix_timestamp())?.details;
+        if details == Details::default() {
+            return None;
+        }
+
+        let settings = ctx.settings;
+        let units = settings.units;
+        let direction = details.wind_direction.map_or(MISSING, compass_point);
+        let value = |speed| wind_value(speed, units, settings.beaufort);
+        let speed = details.wind_speed.map_or(MISSING.to_string(), |speed| {
+            format!("{}{}", value(speed), wind_unit(units, settings.beaufort))
+        });
+        // the unit of the gusts is given by the speed
+        let gust = details
+            .wind_gust
+            .map_or(String::new(), |gust| format!(" g{}", value(gust)));
+        let phrases = settings.locale.phrases();
+        let wind_label = phrases.wind;
+        let wind = fit(
+            ctx,
+            &[
+                format!("{wind_label} {direction:3} {speed}{gust}"),
+                format!("{direction} {speed}{gust}"),
+            ],
+        );
+
+        let humidity = display_percent(details.humidity);
+        let dew_point = display_temperature(details.dew_point, units);
+        let symbol = unit_symbol(units);
+        let (humidity_label, dew) = (phrases.humidity, phrases.dew_point);
+        let humidity = fit(
+            ctx,
+            &[
+                format!("{humidity_label} {humidity:4}  {dew} {dew_point}{symbol}"),
+                format!(
+                    "{humidity_label} {humidity} {dew} {}{symbol}",
+                    dew_point.trim_start()
+                ),
+                format!("{humidity} {dew} {}{symbol}", dew_point.trim_start()),
+            ],
+        );
+
+        let last = match ctx.alternate() {
+            false => format!(
+                "{}  UV {}",
+                display_pressure(details.pressure, units),
+                details
+                    .uv_index
+                    .map_or(MISSING.to_string(), |uv| format!("{uv:.0}")),
+            ),
+            true => {
+                let clouds = display_percent(details.clouds);
+                let visibility = display_visibility(details.visibility, units);
+                let (clouds_label, vis) = (phrases.clouds, phrases.visibility);
+                fit(
+                    ctx,
+                    &[
+                        format!("{clouds_label} {clouds} {vis} {visibility}"),
+                        format!("{clouds} {vis} {visibility}"),
+                    ],
+                )
+            }
+        };
+
+        Some(arrange(ctx, &[clock_row(ctx), wind, humidity, last]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visibility_in_fog() {
+        assert_eq!(display_visibility(Some(400.0), Units::Metric), "400m");
+        assert_eq!(display_visibility(Some(400.0), Units::Imperial), "0.2mi");
+    }
+
+    #[test]
+    fn visibility() {
+        assert_eq!(display_visibility(Some(4500.0), Units::Metric), "4.5km");
+        assert_eq!(display_visibility(Some(24140.0), Units::Metric), "24km");
+        assert_eq!(display_visibility(Some(24140.0), Units::Imperial), "15mi");
+        assert_eq!(display_visibility(None, Units::Metric), "-");
+    }
+}
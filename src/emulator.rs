@@ -0,0 +1,187 @@
+use crate::charset::a00_char;
+use crate::geometry::Geometry;
+use crate::icon::Glyph;
+use lcd::{Delay, Hardware};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Number of bytes of the display data RAM
+const DDRAM_SIZE: usize = 0x80;
+/// Number of bytes of the character generator RAM, eight 5x8 characters
+const CGRAM_SIZE: usize = 0x40;
+
+/// In-memory HD44780 controller with the A00 character ROM. It executes the instructions the
+/// real one receives, so it shows exactly what would be on the glass.
+pub struct Hd44780 {
+    /// The interface starts 8 bits wide after power-on and is switched to 4 bits by a function set
+    four_bit: bool,
+    /// High half of a byte received over the 4-bit interface, waiting for the low half
+    high_nibble: Option<u8>,
+    two_lines: bool,
+    display_on: bool,
+    /// Whether the address counter moves right after every write
+    increment: bool,
+    /// Whether the address counter points to the CGRAM rather than the DDRAM
+    cgram_selected: bool,
+    address: u8,
+    ddram: [u8; DDRAM_SIZE],
+    cgram: [u8; CGRAM_SIZE],
+}
+
+impl Default for Hd44780 {
+    fn default() -> Self {
+        Self {
+            four_bit: false,
+            high_nibble: None,
+            two_lines: false,
+            display_on: false,
+            increment: true,
+            cgram_selected: false,
+            address: 0,
+            ddram: [b' '; DDRAM_SIZE],
+            cgram: [0; CGRAM_SIZE],
+        }
+    }
+}
+
+impl Hd44780 {
+    /// Latch the four data lines D7 to D4, `rs` selects data rather than an instruction
+    fn receive(&mut self, rs: bool, nibble: u8) {
+        if !self.four_bit {
+            // D3 to D0 aren't connected and read as zeroes
+            self.execute(rs, nibble << 4);
+        } else if let Some(high) = self.high_nibble.take() {
+            self.execute(rs, high << 4 | nibble);
+        } else {
+            self.high_nibble = Some(nibble);
+        }
+    }
+
+    fn execute(&mut self, rs: bool, byte: u8) {
+        if rs {
+            self.write(byte);
+        } else if byte & 0x80 != 0 {
+            self.cgram_selected = false;
+            self.address = byte & 0x7F;
+        } else if byte & 0x40 != 0 {
+            self.cgram_selected = true;
+            self.address = byte & 0x3F;
+        } else if byte & 0x20 != 0 {
+            self.four_bit = byte & 0x10 == 0;
+            self.two_lines = byte & 0x08 != 0;
+        } else if byte & 0x10 != 0 {
+            // shifting the whole display isn't used, only moving the cursor is emulated
+            if byte & 0x08 == 0 {
+                self.advance(byte & 0x04 != 0);
+            }
+        } else if byte & 0x08 != 0 {
+            self.display_on = byte & 0x04 != 0;
+        } else if byte & 0x04 != 0 {
+            self.increment = byte & 0x02 != 0;
+        } else if byte & 0x02 != 0 {
+            self.cgram_selected = false;
+            self.address = 0;
+        } else if byte & 0x01 != 0 {
+            self.ddram = [b' '; DDRAM_SIZE];
+            self.cgram_selected = false;
+            self.address = 0;
+            self.increment = true;
+        }
+    }
+
+    fn write(&mut self, byte: u8) {
+        if self.cgram_selected {
+            // only the five columns of a character row are stored
+            self.cgram[usize::from(self.address)] = byte & 0x1F;
+        } else {
+            self.ddram[usize::from(self.address)] = byte;
+        }
+        self.advance(self.increment);
+    }
+
+    /// Move the address counter by one, over the gap between the two DDRAM lines
+    fn advance(&mut self, right: bool) {
+        self.address = match (self.cgram_selected, self.two_lines, right) {
+            (true, _, true) => (self.address + 1) % CGRAM_SIZE as u8,
+            (true, _, false) => self.address.checked_sub(1).unwrap_or(CGRAM_SIZE as u8 - 1),
+            (false, true, true) => match self.address {
+                0x27 => 0x40,
+                0x67 => 0x00,
+                address => address + 1,
+            },
+            (false, true, false) => match self.address {
+                0x00 => 0x67,
+                0x40 => 0x27,
+                address => address - 1,
+            },
+            (false, false, true) => (self.address + 1) % 0x50,
+            (false, false, false) => self.address.checked_sub(1).unwrap_or(0x4F),
+        };
+    }
+
+    /// Rows of the glass of a display of `geometry`. User-defined characters are shown as the
+    /// icons or letters they were loaded with.
+    pub fn glass(&self, geometry: Geometry) -> Vec<String> {
+        (0..geometry.rows)
+            .map(|row| {
+                let start = usize::from(geometry.row_address(row));
+                self.ddram[start..start + geometry.cols]
+                    .iter()
+                    .map(|&code| match (self.display_on, code) {
+                        (false, _) => ' ',
+                        (true, 0x00..=0x0F) => self.user_char(code & 0x07),
+                        (true, code) => a00_char(code),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn user_char(&self, slot: u8) -> char {
+        let start = usize::from(slot) * 8;
+        let bitmap: [u8; 8] = self.cgram[start..start + 8].try_into().unwrap();
+        Glyph::from_bitmap(bitmap).map_or('?', Glyph::to_unicode)
+    }
+}
+
+/// Pins of an emulated display, for driving it through `lcd::Display`
+pub struct Emulator {
+    rs: bool,
+    enable: bool,
+    data: u8,
+    controller: Rc<RefCell<Hd44780>>,
+}
+
+impl Emulator {
+    /// Pins connected to `controller`
+    pub fn new(controller: Rc<RefCell<Hd44780>>) -> Self {
+        Self {
+            rs: false,
+            enable: false,
+            data: 0,
+            controller,
+        }
+    }
+}
+
+impl Hardware for Emulator {
+    fn rs(&mut self, bit: bool) {
+        self.rs = bit;
+    }
+
+    fn enable(&mut self, bit: bool) {
+        // the controller latches the data lines on the falling edge of the enable line
+        if self.enable && !bit {
+            self.controller.borrow_mut().receive(self.rs, self.data);
+        }
+        self.enable = bit;
+    }
+
+    fn data(&mut self, bits: u8) {
+        self.data = bits & 0x0F;
+    }
+}
+
+impl Delay for Emulator {
+    fn delay_us(&mut self, _: u32) {}
+}
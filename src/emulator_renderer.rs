@@ -0,0 +1,55 @@
+use crate::emulator::{Emulator, Hd44780};
+use crate::geometry::Geometry;
+use crate::lcd::Lcd;
+use crate::Text;
+use actix::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Rows of the glass of a display of `geometry` in a frame, one per line
+pub fn framed(glass: &[String], geometry: Geometry) -> String {
+    let border = format!("+{}+\n", "-".repeat(geometry.cols));
+    let rows = glass
+        .iter()
+        .map(|row| format!("|{row}|\n"))
+        .collect::<String>();
+    format!("{border}{rows}{border}")
+}
+
+/// Drives an emulated display like the real one and prints its glass whenever it changes
+#[derive(Default)]
+pub struct EmulatorRenderer {
+    controller: Rc<RefCell<Hd44780>>,
+    /// Initialized display, `None` before the first update
+    lcd: Option<Lcd<Emulator>>,
+    /// Rows of the glass printed last
+    printed: Vec<String>,
+}
+
+impl Actor for EmulatorRenderer {
+    type Context = Context<Self>;
+}
+
+impl Handler<Text> for EmulatorRenderer {
+    type Result = ();
+
+    fn handle(&mut self, text: Text, _: &mut Self::Context) {
+        let result = match &mut self.lcd {
+            Some(lcd) => lcd.show(&text),
+            lcd @ None => {
+                let emulator = Emulator::new(Rc::clone(&self.controller));
+                Lcd::emulated(emulator, text.geometry).and_then(|new| lcd.insert(new).show(&text))
+            }
+        };
+        if let Err(e) = result {
+            eprintln!("emulated display update failed: {e:#}");
+            return;
+        }
+
+        let glass = self.controller.borrow().glass(text.geometry);
+        if glass != self.printed {
+            print!("{}", framed(&glass, text.geometry));
+            self.printed = glass;
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::icon;
+use crate::page::{
+    arrange, clock_row, display_conditions, display_temperature, fit, unit_symbol, Arrangement,
+    Page, PageContext, Status, TIME_FORMAT,
+};
+use crate::slot::SLOT_WIDTH;
+use crate::weather::WeatherData;
+use crate::Text;
+
+/// The clock with the configured forecast slots
+#[derive(Debug)]
+pub struct ForecastPage;
+
+impl Page for ForecastPage {
+    fn render(&self, ctx: &PageContext) -> Option<Text> {
+        let settings = ctx.settings;
+        let arrangement = Arrangement::of(settings.geometry);
+        let width = arrangement.row_width(settings.geometry);
+        let weather_data = WeatherData::at(
+            ctx.forecast,
+            &settings.slots,
+            ctx.now,
+            &settings.timezone,
+            ctx.alternate(),
+        );
+
+        let units = settings.units;
+        let mut temperature = weather_data
+            .entries
+            .iter()
+            .map(|we| display_temperature(we.temperature, units))
+            .collect::<Vec<_>>()
+            .join(" ");
+        // the unit symbol goes after the last column if there's room for it
+        let free = width.saturating_sub(icon::width(&temperature));
+        let symbol = unit_symbol(units);
+        if free >= icon::width(&symbol) {
+            temperature.push_str(&symbol);
+        } else if free >= 1 {
+            temperature.push(units.suffix());
+        }
+
+        // the conditions row explains the indicator unless the weather data is fine
+        let phrases = settings.locale.phrases();
+        let conditions = match (&ctx.status, ctx.error) {
+            (Status::NoData, Some(error)) => format!("{}: {error}", phrases.error),
+            (Status::NoData, None) => phrases.waiting.to_string(),
+            (Status::Stale { since }, _) => {
+                let since = settings
+                    .timezone
+                    .to_local(*since)
+                    .format(&TIME_FORMAT)
+                    .unwrap();
+                fit(
+                    ctx,
+                    &[
+                        format!("{} {since}", phrases.stale_since),
+                        format!("{} {since}", phrases.stale),
+                    ],
+                )
+            }
+            (Status::Fresh, _) => weather_data
+                .entries
+                .iter()
+                .map(|we| display_conditions(&we.conditions) + " ")
+                .collect(),
+        };
+
+        let labels = weather_data
+            .entries
+            .iter()
+            .map(|we| icon::pad(&we.label, SLOT_WIDTH))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let rows = match arrangement {
+            // on two-row displays the labels cycle with the rows they explain
+            Arrangement::Rows | Arrangement::Cycling => {
+                vec![clock_row(ctx), labels, temperature, conditions]
+            }
+            // the labels go above the temperatures in the right half
+            Arrangement::SideBySide => vec![clock_row(ctx), labels, conditions, temperature],
+        };
+        Some(arrange(ctx, &rows))
+    }
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Size of a character display
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Geometry {
+    pub cols: usize,
+    pub rows: usize,
+}
+
+impl Geometry {
+    pub const LCD_20X4: Geometry = Geometry { cols: 20, rows: 4 };
+
+    /// DDRAM address of the first character of `row`. Displays with four rows are two rows of
+    /// the controller split in halves, so the third and fourth rows continue the first two.
+    pub fn row_address(&self, row: usize) -> u8 {
+        let half = (row / 2 * self.cols) as u8;
+        match row % 2 {
+            0 => half,
+            _ => 0x40 + half,
+        }
+    }
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Self::LCD_20X4
+    }
+}
+
+impl FromStr for Geometry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (cols, rows) = match s {
+            "16x2" => (16, 2),
+            "16x4" => (16, 4),
+            "20x2" => (20, 2),
+            "20x4" => (20, 4),
+            "40x2" => (40, 2),
+            _ => return Err(format!("unsupported display size {s:?}")),
+        };
+        Ok(Self { cols, rows })
+    }
+}
+
+impl TryFrom<String> for Geometry {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl configure_me::parse_arg::ParseArgFromStr for Geometry {
+    fn describe_type<W: std::fmt::Write>(mut writer: W) -> std::fmt::Result {
+        write!(writer, "16x2, 16x4, 20x2, 20x4 or 40x2")
+    }
+}
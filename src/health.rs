@@ -0,0 +1,63 @@
+use std::time::{Duration, Instant};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// Successes and failures of something which is retried with an exponential backoff
+#[derive(Debug)]
+pub struct Health {
+    /// How long to back off after the first failure. Doubles with each consecutive failure.
+    base: Duration,
+    max: Duration,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+    pub last_success: Option<OffsetDateTime>,
+    /// Nothing is retried until this instant
+    pub backoff_until: Option<Instant>,
+}
+
+impl Health {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            consecutive_failures: 0,
+            total_failures: 0,
+            last_success: None,
+            backoff_until: None,
+        }
+    }
+
+    pub fn in_backoff(&self) -> bool {
+        self.backoff_until.is_some_and(|b| b > Instant::now())
+    }
+
+    pub fn succeeded(&mut self) {
+        self.consecutive_failures = 0;
+        self.last_success = Some(OffsetDateTime::now_utc());
+        self.backoff_until = None;
+    }
+
+    pub fn failed(&mut self) {
+        self.consecutive_failures += 1;
+        self.total_failures += 1;
+        let backoff = self
+            .base
+            .saturating_mul(1 << (self.consecutive_failures - 1).min(16))
+            .min(self.max);
+        self.backoff_until = Some(Instant::now() + backoff);
+    }
+}
+
+impl std::fmt::Display for Health {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "{} consecutive failures, {} in total",
+            self.consecutive_failures, self.total_failures
+        )?;
+        match self.last_success.and_then(|t| t.format(&Rfc3339).ok()) {
+            Some(last_success) => write!(fmt, ", last success at {last_success}"),
+            None => write!(fmt, ", never succeeded"),
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use crate::charset;
+
+/// Number of user-defined characters of the HD44780
+pub const CGRAM_SLOTS: usize = 8;
+
+/// First private use code point, icons are passed in `Text` as the code points following it
+const FIRST_CODE_POINT: u32 = 0xE000;
+
+/// Custom 5x8 glyphs. The variants are ordered by priority, the first ones get a CGRAM slot when
+/// a screen uses more icons than fit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Icon {
+    Thunder,
+    Wind,
+    Rain,
+    Sleet,
+    Snow,
+    Fog,
+    Cloud,
+    Sun,
+    Moon,
+    Degree,
+}
+
+impl Icon {
+    const ALL: [Icon; 10] = [
+        Icon::Thunder,
+        Icon::Wind,
+        Icon::Rain,
+        Icon::Sleet,
+        Icon::Snow,
+        Icon::Fog,
+        Icon::Cloud,
+        Icon::Sun,
+        Icon::Moon,
+        Icon::Degree,
+    ];
+
+    /// Character standing for the icon in `Text`
+    pub fn to_char(self) -> char {
+        char::from_u32(FIRST_CODE_POINT + self as u32).unwrap()
+    }
+
+    /// The icon `c` stands for, if any
+    pub fn from_char(c: char) -> Option<Self> {
+        let index = (c as u32).checked_sub(FIRST_CODE_POINT)?;
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// Unicode glyph resembling the icon, for terminals
+    pub fn glyph(self) -> char {
+        match self {
+            Icon::Thunder => '↯',
+            Icon::Wind => '≈',
+            Icon::Rain => '☂',
+            Icon::Sleet => '❆',
+            Icon::Snow => '❄',
+            Icon::Fog => '≡',
+            Icon::Cloud => '☁',
+            Icon::Sun => '☼',
+            Icon::Moon => '☾',
+            Icon::Degree => '°',
+        }
+    }
+
+    /// Character of the A00 character ROM shown when the icon didn't get a CGRAM slot
+    pub fn fallback(self) -> u8 {
+        match self {
+            Icon::Thunder => b'T',
+            Icon::Wind => b'W',
+            Icon::Rain => b'R',
+            Icon::Sleet => b'x',
+            Icon::Snow => b'S',
+            Icon::Fog => b'=',
+            Icon::Cloud => b'c',
+            Icon::Sun => b'*',
+            Icon::Moon => b')',
+            Icon::Degree => 0xDF,
+        }
+    }
+
+    /// Rows of the 5x8 glyph, top to bottom
+    pub fn bitmap(self) -> [u8; 8] {
+        match self {
+            Icon::Thunder => [
+                0b00010, 0b00100, 0b01000, 0b11111, 0b00010, 0b00100, 0b01000, 0b00000,
+            ],
+            Icon::Wind => [
+                0b00000, 0b11110, 0b00001, 0b11110, 0b00000, 0b11100, 0b00010, 0b11100,
+            ],
+            Icon::Rain => [
+                0b00100, 0b00100, 0b01110, 0b01110, 0b11111, 0b11111, 0b01110, 0b00000,
+            ],
+            Icon::Sleet => [
+                0b01000, 0b01000, 0b11100, 0b00000, 0b00101, 0b00010, 0b00101, 0b00000,
+            ],
+            Icon::Snow => [
+                0b00100, 0b10101, 0b01110, 0b00100, 0b01110, 0b10101, 0b00100, 0b00000,
+            ],
+            Icon::Fog => [
+                0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000,
+            ],
+            Icon::Cloud => [
+                0b00000, 0b00000, 0b01100, 0b10010, 0b10001, 0b11111, 0b00000, 0b00000,
+            ],
+            Icon::Sun => [
+                0b00100, 0b10101, 0b01110, 0b11111, 0b01110, 0b10101, 0b00100, 0b00000,
+            ],
+            Icon::Moon => [
+                0b00110, 0b01100, 0b11000, 0b11000, 0b11000, 0b01100, 0b00110, 0b00000,
+            ],
+            Icon::Degree => [
+                0b01100, 0b10010, 0b10010, 0b01100, 0b00000, 0b00000, 0b00000, 0b00000,
+            ],
+        }
+    }
+}
+
+/// Character drawn in a CGRAM slot. Icons come first, so they're preferred over letters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Glyph {
+    Icon(Icon),
+    /// Letter missing from the character ROM
+    Letter(char),
+}
+
+impl Glyph {
+    /// The glyph `c` is drawn with, if it needs a CGRAM slot
+    fn of(c: char) -> Option<Self> {
+        match Icon::from_char(c) {
+            Some(icon) => Some(Glyph::Icon(icon)),
+            None if charset::a00_code(c).is_some() => None,
+            None => charset::synthesized_bitmap(c).map(|_| Glyph::Letter(c)),
+        }
+    }
+
+    /// The glyph drawn by `bitmap`, if any
+    pub fn from_bitmap(bitmap: [u8; 8]) -> Option<Self> {
+        let icon = Icon::ALL.into_iter().find(|icon| icon.bitmap() == bitmap);
+        icon.map(Glyph::Icon).or_else(|| {
+            charset::SYNTHESIZED
+                .into_iter()
+                .find(|&c| charset::synthesized_bitmap(c) == Some(bitmap))
+                .map(Glyph::Letter)
+        })
+    }
+
+    /// Character resembling the glyph, for terminals
+    pub fn to_unicode(self) -> char {
+        match self {
+            Glyph::Icon(icon) => icon.glyph(),
+            Glyph::Letter(c) => c,
+        }
+    }
+
+    /// Rows of the 5x8 glyph, top to bottom
+    pub fn bitmap(self) -> [u8; 8] {
+        match self {
+            Glyph::Icon(icon) => icon.bitmap(),
+            Glyph::Letter(c) => charset::synthesized_bitmap(c).unwrap(),
+        }
+    }
+}
+
+/// Number of display cells `c` takes
+pub fn cells(c: char) -> usize {
+    match Icon::from_char(c) {
+        Some(_) => 1,
+        None => charset::cells(c),
+    }
+}
+
+/// Number of display cells `s` takes
+pub fn width(s: &str) -> usize {
+    s.chars().map(cells).sum()
+}
+
+/// The longest start of `s` which fits in `width` cells
+pub fn truncate(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    let end = s
+        .char_indices()
+        .find(|&(_, c)| {
+            used += cells(c);
+            used > width
+        })
+        .map_or(s.len(), |(i, _)| i);
+    &s[..end]
+}
+
+/// `s` cut or padded with spaces to exactly `width` cells
+pub fn pad(s: &str, width: usize) -> String {
+    let s = truncate(s, width);
+    format!("{s}{}", " ".repeat(width - self::width(s)))
+}
+
+/// Icons and letters loaded into the CGRAM slots of the display, in slot order
+#[derive(Default, Debug, PartialEq)]
+pub struct IconSet {
+    glyphs: Vec<Glyph>,
+}
+
+impl IconSet {
+    /// Choose the glyphs for a screen showing `rows`. If the screen uses more glyphs than there
+    /// are CGRAM slots, the ones with the highest priority are loaded and the rest is shown as
+    /// their fallback characters.
+    pub fn for_rows<'a>(rows: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut glyphs = rows
+            .into_iter()
+            .flat_map(str::chars)
+            .filter_map(Glyph::of)
+            .collect::<Vec<_>>();
+        glyphs.sort();
+        glyphs.dedup();
+        glyphs.truncate(CGRAM_SLOTS);
+        Self { glyphs }
+    }
+
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+
+    /// Encode `s` as character codes of the display, with glyphs pointing to their CGRAM slots
+    /// and other characters transliterated to the character ROM
+    pub fn encode(&self, s: &str) -> Vec<u8> {
+        let mut out = Vec::with_capacity(s.len());
+        for c in s.chars() {
+            let glyph = Glyph::of(c);
+            match glyph.and_then(|glyph| self.glyphs.iter().position(|g| *g == glyph)) {
+                Some(slot) => out.push(slot as u8),
+                None => match Icon::from_char(c) {
+                    Some(icon) => out.push(icon.fallback()),
+                    None => charset::transliterate(c, &mut out),
+                },
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn width_counts_cells() {
+        assert_eq!(width("Straße"), 7);
+        assert_eq!(width(&Icon::Degree.to_char().to_string()), 1);
+    }
+
+    #[test]
+    fn truncate_keeps_whole_characters() {
+        assert_eq!(truncate("Straße", 5), "Stra");
+        assert_eq!(truncate("Straße", 7), "Straße");
+        assert_eq!(truncate("Straße", 20), "Straße");
+    }
+
+    #[test]
+    fn pad_fills_cells() {
+        assert_eq!(pad("ß", 3), "ß ");
+        assert_eq!(pad("Straße", 5), "Stra ");
+        assert_eq!(pad("now", 3), "now");
+    }
+}
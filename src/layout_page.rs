@@ -0,0 +1,21 @@
+use crate::page::{Page, PageContext};
+use crate::template::Template;
+use crate::Text;
+
+/// A page laid out by the user
+#[derive(Debug)]
+pub struct LayoutPage {
+    template: Template,
+}
+
+impl LayoutPage {
+    pub fn new(template: Template) -> Self {
+        Self { template }
+    }
+}
+
+impl Page for LayoutPage {
+    fn render(&self, ctx: &PageContext) -> Option<Text> {
+        Some(self.template.render(ctx))
+    }
+}
@@ -1,17 +1,52 @@
-use crate::LimitedString;
-use lcd::Display;
+use crate::emulator::Emulator;
+use crate::geometry::Geometry;
+use crate::icon::IconSet;
+use crate::{LimitedString, Text};
+use lcd::{Delay, Display, Hardware};
 use lcd_pcf8574::{ErrorHandling, Pcf8574};
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
 
-pub struct Lcd {
-    display: Display<Pcf8574>,
+/// Initialized display which keeps track of what's on its glass
+pub struct Lcd<HW: Hardware + Delay = Pcf8574> {
+    display: Display<HW>,
+    geometry: Geometry,
+    /// First bus error since the last check. `lcd::Display` can't return errors, so the
+    /// expander's error handler stores them here.
+    error: Rc<RefCell<Option<anyhow::Error>>>,
+    /// Icons loaded into the CGRAM
+    icons: IconSet,
+    /// Character codes on the glass, one row each
+    shown: Vec<Vec<u8>>,
 }
 
 impl Lcd {
-    pub fn new_i2c(bus: u8, addr: u16) -> anyhow::Result<Self> {
+    pub fn new_i2c(bus: u8, addr: u16, geometry: Geometry) -> anyhow::Result<Self> {
         let mut dev = Pcf8574::new(bus, addr)?;
-        dev.on_error(ErrorHandling::None);
+        let error = Rc::new(RefCell::new(None));
+        let handler_error = Rc::clone(&error);
+        dev.on_error(ErrorHandling::Custom(Box::new(move |e| {
+            handler_error.borrow_mut().get_or_insert(e.into());
+        })));
+        Self::init(dev, geometry, error)
+    }
+}
+
+impl Lcd<Emulator> {
+    pub fn emulated(emulator: Emulator, geometry: Geometry) -> anyhow::Result<Self> {
+        Self::init(emulator, geometry, Rc::default())
+    }
+}
 
-        let mut display = Display::new(dev);
+impl<HW: Hardware + Delay> Lcd<HW> {
+    fn init(
+        hw: HW,
+        geometry: Geometry,
+        error: Rc<RefCell<Option<anyhow::Error>>>,
+    ) -> anyhow::Result<Self> {
+        let mut display = Display::new(hw);
+        // displays with four rows are driven as two long rows of the controller
         display.init(lcd::FunctionLine::Line2, lcd::FunctionDots::Dots5x8);
         display.display(
             lcd::DisplayMode::DisplayOn,
@@ -21,36 +56,149 @@ impl Lcd {
 
         display.clear();
         display.home();
-        Ok(Self { display })
+        let mut lcd = Self {
+            display,
+            geometry,
+            error,
+            icons: IconSet::default(),
+            shown: vec![vec![b' '; geometry.cols]; geometry.rows],
+        };
+        lcd.check()?;
+        Ok(lcd)
     }
 
-    pub fn clear(&mut self) -> anyhow::Result<()> {
-        self.display.clear();
-        self.display.home();
+    /// Bring the glass to `text`, writing only the characters which changed
+    pub fn show(&mut self, text: &Text) -> anyhow::Result<()> {
+        let icons = IconSet::for_rows(text.rows.iter().map(LimitedString::as_str));
+        if icons != self.icons {
+            self.upload_icons(&icons)?;
+            self.icons = icons;
+        }
+
+        for (i, row) in text.rows.iter().enumerate().take(self.shown.len()) {
+            let mut data = self.icons.encode(row.as_str());
+            data.resize(self.geometry.cols, b' ');
+            for run in changed_runs(&self.shown[i], &data) {
+                self.write_at(i, run.start, &data[run.clone()])?;
+                self.shown[i][run.clone()].copy_from_slice(&data[run]);
+            }
+        }
         Ok(())
     }
 
-    pub fn line_1(&mut self, ls: &LimitedString) -> anyhow::Result<()> {
-        self.display.position(0, 0);
-        self.display.print(ls.as_str());
-        Ok(())
+    /// Load `icons` into the CGRAM slots
+    fn upload_icons(&mut self, icons: &IconSet) -> anyhow::Result<()> {
+        for (slot, glyph) in icons.glyphs().iter().enumerate() {
+            self.display.upload_character(slot as u8, glyph.bitmap());
+        }
+        self.check()
     }
 
-    pub fn line_2(&mut self, ls: &LimitedString) -> anyhow::Result<()> {
-        self.display.position(0, 1);
-        self.display.print(ls.as_str());
-        Ok(())
+    /// Write `data` to `row` starting at `col`
+    fn write_at(&mut self, row: usize, col: usize, data: &[u8]) -> anyhow::Result<()> {
+        // the first row starts at address 0, so its column is the raw DDRAM address
+        let address = self.geometry.row_address(row) + col as u8;
+        self.display.position(address, 0);
+        self.write(data);
+        self.check()
     }
 
-    pub fn line_3(&mut self, ls: &LimitedString) -> anyhow::Result<()> {
-        self.display.position(0, 2);
-        self.display.print(ls.as_str());
-        Ok(())
+    /// Fail with the bus error which occurred since the last check, if any
+    fn check(&mut self) -> anyhow::Result<()> {
+        match self.error.borrow_mut().take() {
+            Some(e) => Err(e.context("I2C write failed")),
+            None => Ok(()),
+        }
     }
 
-    pub fn line_4(&mut self, ls: &LimitedString) -> anyhow::Result<()> {
-        self.display.position(0, 3);
-        self.display.print(ls.as_str());
-        Ok(())
+    /// Write character codes, unlike `Display::print` which writes UTF-8
+    fn write(&mut self, data: &[u8]) {
+        for byte in data {
+            self.display.write(*byte);
+        }
+    }
+}
+
+/// Ranges of consecutive characters in which `new` differs from `old`
+fn changed_runs(old: &[u8], new: &[u8]) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, (old, new)) in old.iter().zip(new).enumerate() {
+        match (start, old == new) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                runs.push(s..i);
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(s) = start {
+        runs.push(s..old.len().min(new.len()));
+    }
+    runs
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+    use crate::emulator::Hd44780;
+    use std::rc::Rc;
+
+    #[test]
+    fn nothing_changed() {
+        assert_eq!(changed_runs(b"12:30:05", b"12:30:05"), []);
+    }
+
+    #[test]
+    fn run_at_the_start() {
+        assert_eq!(changed_runs(b"12:30:05", b"23:30:05"), [0..2]);
+    }
+
+    #[test]
+    fn run_at_the_end() {
+        assert_eq!(changed_runs(b"12:30:05", b"12:30:06"), [7..8]);
+        assert_eq!(changed_runs(b"12:30:05", b"12:30:16"), [6..8]);
+    }
+
+    #[test]
+    fn several_runs() {
+        assert_eq!(changed_runs(b"12:59:59", b"13:00:00"), [1..2, 3..5, 6..8]);
+    }
+
+    #[test]
+    fn whole_row() {
+        assert_eq!(changed_runs(b"abcd", b"efgh"), [0..4]);
+    }
+
+    #[test]
+    fn glyph_changing_width() {
+        // ß takes two cells, shifting the rest of the row
+        let icons = IconSet::default();
+        let mut old = icons.encode("ß 12");
+        let mut new = icons.encode("ss 12");
+        old.resize(8, b' ');
+        new.resize(8, b' ');
+        assert_eq!(changed_runs(&old, &new), []);
+
+        let mut new = icons.encode("s 12");
+        new.resize(8, b' ');
+        assert_eq!(changed_runs(&old, &new), [1..5]);
+    }
+
+    #[test]
+    fn shows_changes() {
+        let geometry = "16x2".parse().unwrap();
+        let controller = Rc::new(std::cell::RefCell::new(Hd44780::default()));
+        let mut lcd = Lcd::emulated(Emulator::new(Rc::clone(&controller)), geometry).unwrap();
+        let mut text = Text::new(geometry);
+        for (first, second) in [("12:59:59", "Straße"), ("13:00:00", "Strae")] {
+            text.set_row(0, first);
+            text.set_row(1, second);
+            lcd.show(&text).unwrap();
+        }
+        let glass = controller.borrow().glass(geometry);
+        assert_eq!(glass, ["13:00:00        ", "Strae           "]);
     }
 }
@@ -1,11 +1,22 @@
+use crate::health::Health;
+use crate::lcd::Lcd;
 use crate::Text;
 use actix::prelude::*;
 use std::time::Duration;
 
+/// How long the display is left alone after its first failure
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// How often the health of the display is logged
+const HEALTH_INTERVAL: Duration = Duration::from_secs(3600);
+
 pub struct LcdRenderer {
     bus: u8,
     addr: u16,
-    text: Text,
+    /// Initialized display, `None` before the first update and after a failed one
+    lcd: Option<Lcd>,
+    health: Health,
 }
 
 impl LcdRenderer {
@@ -13,40 +24,19 @@ impl LcdRenderer {
         Self {
             bus,
             addr,
-            text: Text::default(),
+            lcd: None,
+            health: Health::new(BASE_BACKOFF, MAX_BACKOFF),
         }
     }
 }
 
-#[derive(Default, Message)]
-#[rtype(result = "()")]
-pub struct Tick;
-
 impl Actor for LcdRenderer {
     type Context = Context<Self>;
 
     fn started(&mut self, ctx: &mut Self::Context) {
-        ctx.notify(Tick);
-    }
-}
-
-impl Handler<Tick> for LcdRenderer {
-    type Result = ();
-
-    fn handle(&mut self, _: Tick, ctx: &mut Self::Context) {
-        // explicitly discard the result of LCD write. If this update failed, the next one will
-        // likely succeed
-        let _ = (|| -> Result<_, anyhow::Error> {
-            let mut lcd = crate::lcd::Lcd::new_i2c(self.bus, self.addr)?;
-            lcd.clear()?;
-            lcd.line_1(&self.text.rows[0])?;
-            lcd.line_2(&self.text.rows[1])?;
-            lcd.line_3(&self.text.rows[2])?;
-            lcd.line_4(&self.text.rows[3])?;
-            Ok(())
-        })();
-
-        ctx.notify_later(Tick, Duration::from_secs(30));
+        ctx.run_interval(HEALTH_INTERVAL, |renderer, _| {
+            eprintln!("display health: {}", renderer.health);
+        });
     }
 }
 
@@ -54,6 +44,32 @@ impl Handler<Text> for LcdRenderer {
     type Result = ();
 
     fn handle(&mut self, text: Text, _: &mut Self::Context) {
-        self.text = text;
+        // while backing off, the updates are dropped, the next one after it shows the latest text
+        if self.lcd.is_none() && self.health.in_backoff() {
+            return;
+        }
+
+        let result = match &mut self.lcd {
+            Some(lcd) => lcd.show(&text),
+            lcd @ None => Lcd::new_i2c(self.bus, self.addr, text.geometry)
+                .and_then(|new| lcd.insert(new).show(&text)),
+        };
+        match result {
+            Ok(()) => {
+                if self.health.consecutive_failures > 0 {
+                    eprintln!(
+                        "display recovered after {} consecutive failures",
+                        self.health.consecutive_failures
+                    );
+                }
+                self.health.succeeded();
+            }
+            Err(e) => {
+                // the display is initialized again once the backoff is over
+                self.lcd = None;
+                self.health.failed();
+                eprintln!("display update failed ({}): {e:#}", self.health);
+            }
+        }
     }
 }
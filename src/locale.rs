@@ -0,0 +1,455 @@
+use serde::Deserialize;
+use std::str::FromStr;
+use time::{Month, Weekday};
+
+/// Language of the weekday and month names, of the texts on the pages and of the slot labels
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Locale {
+    English,
+    Czech,
+    Slovak,
+    German,
+    French,
+    Spanish,
+}
+
+impl Locale {
+    /// Abbreviated name of `weekday`, at most three characters long
+    pub fn weekday(&self, weekday: Weekday) -> &'static str {
+        let names = match self {
+            Locale::English => ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+            Locale::Czech => ["po", "út", "st", "čt", "pá", "so", "ne"],
+            Locale::Slovak => ["po", "ut", "st", "št", "pi", "so", "ne"],
+            Locale::German => ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+            Locale::French => ["lun", "mar", "mer", "jeu", "ven", "sam", "dim"],
+            Locale::Spanish => ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+        };
+        names[weekday.number_days_from_monday() as usize]
+    }
+
+    /// Abbreviated name of `month`, at most three characters long
+    pub fn month(&self, month: Month) -> &'static str {
+        let names = match self {
+            Locale::English => [
+                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+            ],
+            Locale::Czech => [
+                "led", "úno", "bře", "dub", "kvě", "čvn", "čvc", "srp", "zář", "říj", "lis", "pro",
+            ],
+            Locale::Slovak => [
+                "jan", "feb", "mar", "apr", "máj", "jún", "júl", "aug", "sep", "okt", "nov", "dec",
+            ],
+            Locale::German => [
+                "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+            ],
+            Locale::French => [
+                "jan", "fév", "mar", "avr", "mai", "jun", "jul", "aoû", "sep", "oct", "nov", "déc",
+            ],
+            Locale::Spanish => [
+                "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+            ],
+        };
+        names[month as usize - 1]
+    }
+
+    /// Default labels of the slots, at most three characters long
+    pub fn labels(&self) -> &'static Labels {
+        match self {
+            Locale::English => &Labels {
+                now: "now",
+                tomorrow: "tmr",
+                min: "min",
+                max: "max",
+                day: "day",
+                night: "ngt",
+                tomorrow_min: "tmn",
+                tomorrow_max: "tmx",
+                tomorrow_night: "tng",
+            },
+            Locale::Czech => &Labels {
+                now: "teď",
+                tomorrow: "zít",
+                min: "min",
+                max: "max",
+                day: "den",
+                night: "noc",
+                tomorrow_min: "zmn",
+                tomorrow_max: "zmx",
+                tomorrow_night: "zno",
+            },
+            Locale::Slovak => &Labels {
+                now: "ter",
+                tomorrow: "zaj",
+                min: "min",
+                max: "max",
+                day: "deň",
+                night: "noc",
+                tomorrow_min: "zmn",
+                tomorrow_max: "zmx",
+                tomorrow_night: "zno",
+            },
+            Locale::German => &Labels {
+                now: "jzt",
+                tomorrow: "mrg",
+                min: "min",
+                max: "max",
+                day: "Tag",
+                night: "Nac",
+                tomorrow_min: "mmn",
+                tomorrow_max: "mmx",
+                tomorrow_night: "mna",
+            },
+            Locale::French => &Labels {
+                now: "mnt",
+                tomorrow: "dem",
+                min: "min",
+                max: "max",
+                day: "jr",
+                night: "nui",
+                tomorrow_min: "dmn",
+                tomorrow_max: "dmx",
+                tomorrow_night: "dnu",
+            },
+            Locale::Spanish => &Labels {
+                now: "ya",
+                tomorrow: "mañ",
+                min: "mín",
+                max: "máx",
+                day: "día",
+                night: "noc",
+                tomorrow_min: "mmn",
+                tomorrow_max: "mmx",
+                tomorrow_night: "mno",
+            },
+        }
+    }
+
+    /// Words and phrases shown on the pages
+    pub fn phrases(&self) -> &'static Phrases {
+        match self {
+            Locale::English => &Phrases {
+                waiting: "waiting for data",
+                stale_since: "stale since",
+                stale: "stale",
+                error: "error",
+                now: "now",
+                today: "today",
+                low: "low",
+                high: "high",
+                feels: "feels",
+                sunrise: "sunrise",
+                sunset: "sunset",
+                moon: "moon",
+                moon_phases: [
+                    "new moon",
+                    "waxing crescent",
+                    "first quarter",
+                    "waxing gibbous",
+                    "full moon",
+                    "waning gibbous",
+                    "last quarter",
+                    "waning crescent",
+                ],
+                alert: "alert",
+                until: "until",
+                wind: "wind",
+                humidity: "hum",
+                dew_point: "dew",
+                clouds: "cloud",
+                visibility: "vis",
+            },
+            Locale::Czech => &Phrases {
+                waiting: "čekám na data",
+                stale_since: "stará od",
+                stale: "stará",
+                error: "chyba",
+                now: "teď",
+                today: "dnes",
+                low: "min",
+                high: "max",
+                feels: "pocit",
+                sunrise: "východ",
+                sunset: "západ",
+                moon: "měsíc",
+                moon_phases: [
+                    "nov",
+                    "rostoucí srpek",
+                    "první čtvrť",
+                    "dorůstající",
+                    "úplněk",
+                    "couvající",
+                    "poslední čtvrť",
+                    "ubývající srpek",
+                ],
+                alert: "výstraha",
+                until: "do",
+                wind: "vítr",
+                humidity: "vlh",
+                dew_point: "rosa",
+                clouds: "obl",
+                visibility: "dohl",
+            },
+            Locale::Slovak => &Phrases {
+                waiting: "čakám na dáta",
+                stale_since: "staré od",
+                stale: "staré",
+                error: "chyba",
+                now: "teraz",
+                today: "dnes",
+                low: "min",
+                high: "max",
+                feels: "pocit",
+                sunrise: "východ",
+                sunset: "západ",
+                moon: "mesiac",
+                moon_phases: [
+                    "nov",
+                    "rastúci kosák",
+                    "prvá štvrť",
+                    "dorastajúci",
+                    "spln",
+                    "ubúdajúci",
+                    "posledná štvrť",
+                    "ubúdajúci kosák",
+                ],
+                alert: "výstraha",
+                until: "do",
+                wind: "vietor",
+                humidity: "vlh",
+                dew_point: "rosa",
+                clouds: "obl",
+                visibility: "dohľ",
+            },
+            Locale::German => &Phrases {
+                waiting: "warte auf Daten",
+                stale_since: "veraltet seit",
+                stale: "veraltet",
+                error: "Fehler",
+                now: "jetzt",
+                today: "heute",
+                low: "min",
+                high: "max",
+                feels: "gefühlt",
+                sunrise: "Aufgang",
+                sunset: "Untergang",
+                moon: "Mond",
+                moon_phases: [
+                    "Neumond",
+                    "zun. Sichel",
+                    "erstes Viertel",
+                    "zunehmend",
+                    "Vollmond",
+                    "abnehmend",
+                    "letztes Viertel",
+                    "abn. Sichel",
+                ],
+                alert: "Warnung",
+                until: "bis",
+                wind: "Wind",
+                humidity: "Feu",
+                dew_point: "Tau",
+                clouds: "Wolken",
+                visibility: "Sicht",
+            },
+            Locale::French => &Phrases {
+                waiting: "attente données",
+                stale_since: "périmé depuis",
+                stale: "périmé",
+                error: "erreur",
+                now: "actuel",
+                today: "auj.",
+                low: "min",
+                high: "max",
+                feels: "ressenti",
+                sunrise: "lever",
+                sunset: "coucher",
+                moon: "lune",
+                moon_phases: [
+                    "nouvelle lune",
+                    "1er croissant",
+                    "1er quartier",
+                    "gibbeuse crois.",
+                    "pleine lune",
+                    "gibbeuse décr.",
+                    "dern. quartier",
+                    "dern. croissant",
+                ],
+                alert: "alerte",
+                until: "jusqu'à",
+                wind: "vent",
+                humidity: "hum",
+                dew_point: "rosée",
+                clouds: "nuages",
+                visibility: "vis",
+            },
+            Locale::Spanish => &Phrases {
+                waiting: "esperando datos",
+                stale_since: "antiguo desde",
+                stale: "antiguo",
+                error: "error",
+                now: "ahora",
+                today: "hoy",
+                low: "mín",
+                high: "máx",
+                feels: "sensación",
+                sunrise: "salida",
+                sunset: "puesta",
+                moon: "luna",
+                moon_phases: [
+                    "luna nueva",
+                    "creciente",
+                    "primer cuarto",
+                    "gib. creciente",
+                    "luna llena",
+                    "gib. menguante",
+                    "último cuarto",
+                    "menguante",
+                ],
+                alert: "alerta",
+                until: "hasta",
+                wind: "viento",
+                humidity: "hum",
+                dew_point: "rocío",
+                clouds: "nubes",
+                visibility: "vis",
+            },
+        }
+    }
+
+    /// Value of the `lang` parameter of the OpenWeatherMap API
+    pub fn owm_lang(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            // OpenWeatherMap uses the country code for Czech
+            Locale::Czech => "cz",
+            Locale::Slovak => "sk",
+            Locale::German => "de",
+            Locale::French => "fr",
+            Locale::Spanish => "es",
+        }
+    }
+}
+
+/// Slot labels of a locale
+pub struct Labels {
+    pub now: &'static str,
+    /// Tomorrow's day temperature
+    pub tomorrow: &'static str,
+    /// Today's temperatures
+    pub min: &'static str,
+    pub max: &'static str,
+    pub day: &'static str,
+    pub night: &'static str,
+    /// Tomorrow's other temperatures, telling them apart from today's
+    pub tomorrow_min: &'static str,
+    pub tomorrow_max: &'static str,
+    pub tomorrow_night: &'static str,
+}
+
+/// Words and phrases of the pages in a locale
+pub struct Phrases {
+    /// Shown until the first forecast arrives, at most 16 characters long
+    pub waiting: &'static str,
+    pub stale_since: &'static str,
+    /// Shorter variant of `stale_since` for narrow displays
+    pub stale: &'static str,
+    pub error: &'static str,
+    pub now: &'static str,
+    pub today: &'static str,
+    pub low: &'static str,
+    pub high: &'static str,
+    pub feels: &'static str,
+    pub sunrise: &'static str,
+    pub sunset: &'static str,
+    pub moon: &'static str,
+    /// Names of the eight moon phases from the new moon on, at most 15 characters long
+    pub moon_phases: [&'static str; 8],
+    pub alert: &'static str,
+    pub until: &'static str,
+    pub wind: &'static str,
+    pub humidity: &'static str,
+    pub dew_point: &'static str,
+    pub clouds: &'static str,
+    pub visibility: &'static str,
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "en" => Ok(Locale::English),
+            "cs" => Ok(Locale::Czech),
+            "sk" => Ok(Locale::Slovak),
+            "de" => Ok(Locale::German),
+            "fr" => Ok(Locale::French),
+            "es" => Ok(Locale::Spanish),
+            _ => Err(format!("unknown locale {s:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for Locale {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl configure_me::parse_arg::ParseArgFromStr for Locale {
+    fn describe_type<W: std::fmt::Write>(mut writer: W) -> std::fmt::Result {
+        write!(writer, "en, cs, sk, de, fr or es")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::icon::width;
+
+    const LOCALES: [Locale; 6] = [
+        Locale::English,
+        Locale::Czech,
+        Locale::Slovak,
+        Locale::German,
+        Locale::French,
+        Locale::Spanish,
+    ];
+
+    #[test]
+    fn phrases_fit_the_narrowest_display() {
+        for locale in LOCALES {
+            let phrases = locale.phrases();
+            assert!(width(phrases.waiting) <= 16, "{locale:?}");
+            // the width of the moon placeholder of layouts
+            for phase in phrases.moon_phases {
+                assert!(width(phase) <= 15, "{locale:?} {phase}");
+            }
+        }
+    }
+
+    #[test]
+    fn labels_fit_the_slots() {
+        for locale in LOCALES {
+            let labels = locale.labels();
+            for label in [
+                labels.now,
+                labels.tomorrow,
+                labels.min,
+                labels.max,
+                labels.day,
+                labels.night,
+                labels.tomorrow_min,
+                labels.tomorrow_max,
+                labels.tomorrow_night,
+            ] {
+                assert!(
+                    width(label) <= crate::slot::SLOT_WIDTH,
+                    "{locale:?} {label}"
+                );
+            }
+        }
+    }
+}
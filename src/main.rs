@@ -1,11 +1,41 @@
+mod alert_page;
+mod cache;
+mod charset;
+mod condition;
+mod details_page;
+mod emulator;
+mod emulator_renderer;
+mod forecast_page;
+mod geometry;
+mod health;
+mod icon;
+mod layout_page;
 mod lcd;
 mod lcd_renderer;
+mod locale;
+mod met_no;
+mod nws;
+mod open_meteo;
 mod owm;
+mod owm25;
+mod page;
+mod provider;
 mod shutdown_monitor;
+mod slot;
+#[cfg(test)]
+mod snapshots;
 mod source;
 mod stdout_renderer;
+mod sun_page;
+mod temperature;
+mod template;
+mod time_format;
+mod timezone;
+mod today_page;
 mod weather;
+mod wind;
 
+/// A row of the display cut to its width in display cells
 #[derive(Default, Clone, Debug)]
 pub struct LimitedString(String);
 
@@ -13,39 +43,44 @@ impl LimitedString {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
-}
-
-use actix::prelude::*;
-use std::str::FromStr;
-
-impl FromStr for LimitedString {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() > 20 {
-            return Err(());
-        }
 
-        Ok(Self(s.to_string()))
+    /// Keep as much of `s` as fits in `width` cells, see `icon::cells`
+    pub fn truncated(s: &str, width: usize) -> Self {
+        Self(icon::truncate(s, width).to_string())
     }
 }
 
+use actix::prelude::*;
+
 impl std::fmt::Display for LimitedString {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         write!(fmt, "{}", self.0)
     }
 }
 
-impl<'a> Into<ratatui::text::Text<'a>> for &'a LimitedString {
-    fn into(self) -> ratatui::text::Text<'a> {
-        self.0.as_str().into()
-    }
-}
-
+/// Contents of the whole display
 #[derive(Default, Message)]
 #[rtype(result = "()")]
 pub struct Text {
-    rows: [LimitedString; 4],
+    geometry: geometry::Geometry,
+    rows: Vec<LimitedString>,
+}
+
+impl Text {
+    /// Blank text for a display of `geometry`
+    pub fn new(geometry: geometry::Geometry) -> Self {
+        Self {
+            geometry,
+            rows: vec![LimitedString::default(); geometry.rows],
+        }
+    }
+
+    /// Show as much of `s` as fits in `row`, rows past the bottom of the display are ignored
+    pub fn set_row(&mut self, row: usize, s: &str) {
+        if let Some(row) = self.rows.get_mut(row) {
+            *row = LimitedString::truncated(s, self.geometry.cols);
+        }
+    }
 }
 
 /// I2C LCD help: https://www.circuitbasics.com/raspberry-pi-i2c-lcd-set-up-and-programming/
@@ -54,34 +89,80 @@ pub struct Text {
 /// Run the weather clock
 #[derive(Debug)]
 struct ClockOperator {
-    /// Geographical latitude of the place to show weather data for
-    latitude: String,
+    /// Comma-separated names of the weather providers to fetch forecasts from, in order of
+    /// preference
+    provider: String,
 
-    /// Geographical longitude of the place to show weather data for
-    longitude: String,
+    /// Location, units and credentials passed to the weather provider
+    provider_settings: provider::ProviderSettings,
 
-    /// OpenWeatherMap API key.
-    /// See https://home.openweathermap.org/api_keys for more information.
-    appid: String,
+    /// Directory to keep the last successful forecast in
+    state_dir: std::path::PathBuf,
 
-    /// Units can be: standard, metric or imperial.
-    units: String,
+    /// What to show on the display and how
+    display: source::Settings,
 
     run_mode: RunMode,
 }
 
-impl From<Config> for ClockOperator {
-    fn from(c: Config) -> Self {
-        Self {
-            latitude: c.latitude,
-            longitude: c.longitude,
-            appid: c.appid,
-            units: c.units,
-            run_mode: RunMode::Lcd {
-                bus: c.bus,
-                addr: c.addr,
+impl TryFrom<Config> for ClockOperator {
+    type Error = anyhow::Error;
+
+    fn try_from(c: Config) -> anyhow::Result<Self> {
+        let slots = c
+            .slots
+            .as_deref()
+            .unwrap_or(slot::default_slots(c.geometry));
+        let slots = slot::parse_slots(slots, c.temperature, c.locale, c.geometry)?;
+        let layout = c
+            .layout
+            .as_deref()
+            .map(|layout| template::Template::compile(layout, slots.len(), c.geometry))
+            .transpose()
+            .map_err(|e| e.context("invalid layout"))?;
+        let clock_format = page::clock_format(c.clock_format, c.geometry)
+            .map_err(|e| e.context("invalid clock format"))?;
+        let refresh = layout
+            .as_ref()
+            .and_then(template::Template::resolution)
+            .map_or(clock_format.resolution(), |layout| {
+                layout.min(clock_format.resolution())
+            });
+
+        let timezone = timezone::LocalTimeZone::load(c.timezone.as_deref())?;
+
+        Ok(Self {
+            provider: c.provider,
+            provider_settings: provider::ProviderSettings {
+                latitude: c.latitude,
+                longitude: c.longitude,
+                appid: c.appid,
+                user_agent: c.user_agent,
+                locale: c.locale,
+                timezone: timezone.clone(),
             },
-        }
+            state_dir: c.state_dir,
+            display: source::Settings {
+                stale_after: std::time::Duration::from_secs(c.stale_after * 60),
+                slots,
+                timezone,
+                units: c.units,
+                temperature: c.temperature,
+                pages: page::parse_pages(&c.pages, layout.as_ref())?,
+                geometry: c.geometry,
+                locale: c.locale,
+                clock_format,
+                refresh,
+                beaufort: c.beaufort,
+            },
+            run_mode: match c.emulate {
+                true => RunMode::Emulator,
+                false => RunMode::Lcd {
+                    bus: c.bus,
+                    addr: c.addr,
+                },
+            },
+        })
     }
 }
 
@@ -99,20 +180,20 @@ enum RunMode {
     /// Run via terminal
     #[allow(unused)]
     Terminal,
+
+    /// Run using an emulated LCD screen printed to the standard output
+    Emulator,
 }
 
 impl ClockOperator {
-    async fn run_terminal(self) {
+    async fn run_terminal(self, weather_provider: Box<dyn provider::WeatherProvider>) {
+        let cache = crate::cache::ForecastCache::in_dir(&self.state_dir);
         let renderer = crate::stdout_renderer::StdoutRenderer::default().start();
-        let source = crate::source::Source::with_subscriber(renderer.recipient()).start();
-        let _weather = crate::weather::Weather::with_subscriber(
-            source.recipient(),
-            self.latitude,
-            self.longitude,
-            self.units,
-            self.appid,
-        )
-        .start();
+        let source =
+            crate::source::Source::with_subscriber(renderer.recipient(), self.display).start();
+        let _weather =
+            crate::weather::Weather::with_subscriber(source.recipient(), weather_provider, cache)
+                .start();
 
         let (tx, mut rx) = tokio::sync::mpsc::channel(1);
         let _shutdown_monitor = crate::shutdown_monitor::TerminalShutdownMonitor { tx }.start();
@@ -120,17 +201,34 @@ impl ClockOperator {
         rx.recv().await;
     }
 
-    async fn run_lcd(self, bus: u8, addr: u16) {
+    async fn run_emulator(self, weather_provider: Box<dyn provider::WeatherProvider>) {
+        let cache = crate::cache::ForecastCache::in_dir(&self.state_dir);
+        let renderer = crate::emulator_renderer::EmulatorRenderer::default().start();
+        let source =
+            crate::source::Source::with_subscriber(renderer.recipient(), self.display).start();
+        let _weather =
+            crate::weather::Weather::with_subscriber(source.recipient(), weather_provider, cache)
+                .start();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        let _shutdown_monitor = crate::shutdown_monitor::UnixSignalShutdownMonitor { tx }.start();
+
+        rx.recv().await;
+    }
+
+    async fn run_lcd(
+        self,
+        weather_provider: Box<dyn provider::WeatherProvider>,
+        bus: u8,
+        addr: u16,
+    ) {
+        let cache = crate::cache::ForecastCache::in_dir(&self.state_dir);
         let renderer = crate::lcd_renderer::LcdRenderer::using_bus_and_addr(bus, addr).start();
-        let source = crate::source::Source::with_subscriber(renderer.recipient()).start();
-        let _weather = crate::weather::Weather::with_subscriber(
-            source.recipient(),
-            self.latitude,
-            self.longitude,
-            self.units,
-            self.appid,
-        )
-        .start();
+        let source =
+            crate::source::Source::with_subscriber(renderer.recipient(), self.display).start();
+        let _weather =
+            crate::weather::Weather::with_subscriber(source.recipient(), weather_provider, cache)
+                .start();
 
         let (tx, mut rx) = tokio::sync::mpsc::channel(1);
         let _shutdown_monitor = crate::shutdown_monitor::UnixSignalShutdownMonitor { tx }.start();
@@ -141,16 +239,39 @@ impl ClockOperator {
 
 #[macro_use]
 extern crate configure_me;
-configure_me::include_config!();
+
+// Expanded `configure_me::include_config!()`, so that lints can be silenced for the generated code
+#[allow(clippy::all)]
+mod config {
+    #![allow(unused)]
+
+    include!(concat!(env!("OUT_DIR"), "/configure_me_config.rs"));
+}
+
+use config::prelude::*;
 
 #[actix_rt::main]
 async fn main() {
     let (config, _) =
         Config::including_optional_config_files(&["/etc/weather-clock.toml"]).unwrap_or_exit();
-    let co = ClockOperator::from(config);
+    let co = match ClockOperator::try_from(config) {
+        Ok(co) => co,
+        Err(e) => {
+            eprintln!("{e:#}");
+            std::process::exit(1);
+        }
+    };
+    let weather_provider = match provider::from_names(&co.provider, &co.provider_settings) {
+        Ok(weather_provider) => Box::new(weather_provider),
+        Err(e) => {
+            eprintln!("{e:#}");
+            std::process::exit(1);
+        }
+    };
     match co.run_mode {
-        RunMode::Terminal => co.run_terminal().await,
-        RunMode::Lcd { bus, addr } => co.run_lcd(bus, addr).await,
+        RunMode::Terminal => co.run_terminal(weather_provider).await,
+        RunMode::Emulator => co.run_emulator(weather_provider).await,
+        RunMode::Lcd { bus, addr } => co.run_lcd(weather_provider, bus, addr).await,
     };
 
     System::current().stop();
@@ -0,0 +1,477 @@
+use crate::condition::{Cloudiness, Conditions, Intensity, Obscuration};
+use crate::provider::{FetchFuture, ProviderSettings, WeatherProvider};
+use crate::temperature::Temperature;
+use crate::timezone::LocalTimeZone;
+use crate::weather::{DailySample, Details, Forecast, Sample};
+use reqwest::header::{EXPIRES, IF_MODIFIED_SINCE, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::time::SystemTime;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// Last response from the service, kept to honour its caching headers
+struct Cached {
+    forecast: Forecast,
+    expires: Option<SystemTime>,
+    last_modified: Option<String>,
+}
+
+/// MET Norway Locationforecast 2.0 provider. Doesn't need an API key, but requires a User-Agent
+/// identifying the application. See https://api.met.no/doc/TermsOfService for more information.
+pub struct MetNoProvider {
+    client: reqwest::Client,
+    url: String,
+    timezone: LocalTimeZone,
+    cached: RefCell<Option<Cached>>,
+}
+
+impl MetNoProvider {
+    pub fn new(settings: &ProviderSettings) -> anyhow::Result<Self> {
+        let ProviderSettings {
+            latitude,
+            longitude,
+            user_agent,
+            timezone,
+            ..
+        } = settings;
+        let Some(user_agent) = user_agent else {
+            anyhow::bail!("the met-no provider requires a user_agent");
+        };
+        Ok(Self {
+            client: reqwest::Client::builder().user_agent(user_agent).build()?,
+            url: format!("https://api.met.no/weatherapi/locationforecast/2.0/compact?lat={latitude}&lon={longitude}"),
+            timezone: timezone.clone(),
+            cached: RefCell::new(None),
+        })
+    }
+
+    async fn fetch_forecast(&self) -> anyhow::Result<Forecast> {
+        let last_modified = match &*self.cached.borrow() {
+            Some(cached) if cached.expires.is_some_and(|e| e > SystemTime::now()) => {
+                return Ok(cached.forecast.clone());
+            }
+            Some(cached) => cached.last_modified.clone(),
+            None => None,
+        };
+
+        let mut request = self.client.get(&self.url);
+        if let Some(last_modified) = last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = request.send().await?.error_for_status()?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let expires = header(EXPIRES).and_then(|e| httpdate::parse_http_date(&e).ok());
+        let last_modified = header(LAST_MODIFIED);
+
+        let forecast = if response.status() == StatusCode::NOT_MODIFIED {
+            match &*self.cached.borrow() {
+                Some(cached) => cached.forecast.clone(),
+                None => anyhow::bail!("met-no reported an unknown forecast as not modified"),
+            }
+        } else {
+            let root: Root = response.json().await?;
+            root.forecast(&self.timezone, OffsetDateTime::now_utc())?
+        };
+
+        self.cached.replace(Some(Cached {
+            forecast: forecast.clone(),
+            expires,
+            last_modified,
+        }));
+        Ok(forecast)
+    }
+}
+
+impl WeatherProvider for MetNoProvider {
+    fn fetch(&self) -> FetchFuture<'_> {
+        Box::pin(self.fetch_forecast())
+    }
+}
+
+/// Conditions of a symbol code such as `heavyrainandthunder` or `lightsnowshowers_day`, the
+/// variant tells whether the sun is up
+pub fn conditions(symbol_code: &str) -> Conditions {
+    // symbol codes look like lightrainshowersandthunder_day
+    let (code, variant) = symbol_code.split_once('_').unwrap_or((symbol_code, ""));
+    // legacy codes with a typo which are still sent
+    let code = match code {
+        "lightssleetshowersandthunder" => "lightsleetshowersandthunder",
+        "lightssnowshowersandthunder" => "lightsnowshowersandthunder",
+        code => code,
+    };
+    let (intensity, code) = if let Some(code) = code.strip_prefix("light") {
+        (Intensity::Light, code)
+    } else if let Some(code) = code.strip_prefix("heavy") {
+        (Intensity::Heavy, code)
+    } else {
+        (Intensity::Moderate, code)
+    };
+    let (thunder, code) = match code.strip_suffix("andthunder") {
+        Some(code) => (Some(Intensity::Moderate), code),
+        None => (None, code),
+    };
+    let (showers, code) = match code.strip_suffix("showers") {
+        Some(code) => (true, code),
+        None => (false, code),
+    };
+
+    let conditions = Conditions {
+        thunder,
+        showers,
+        // the sun stays below the horizon in the polar twilight too
+        night: variant == "night" || variant == "polartwilight",
+        ..Default::default()
+    };
+    match code {
+        "clearsky" => Conditions {
+            clouds: Some(Cloudiness::Clear),
+            ..conditions
+        },
+        "fair" => Conditions {
+            clouds: Some(Cloudiness::Few),
+            ..conditions
+        },
+        "partlycloudy" => Conditions {
+            clouds: Some(Cloudiness::Scattered),
+            ..conditions
+        },
+        "cloudy" => Conditions {
+            clouds: Some(Cloudiness::Overcast),
+            ..conditions
+        },
+        "fog" => Conditions {
+            obscuration: Some(Obscuration::Fog),
+            ..conditions
+        },
+        "rain" => Conditions {
+            rain: Some(intensity),
+            ..conditions
+        },
+        "sleet" => Conditions {
+            rain: Some(intensity),
+            snow: Some(intensity),
+            ..conditions
+        },
+        "snow" => Conditions {
+            snow: Some(intensity),
+            ..conditions
+        },
+        _ => conditions,
+    }
+}
+
+impl Timeseries {
+    /// The most detailed symbol code available for this point in time
+    fn symbol_code(&self) -> Option<&str> {
+        [
+            &self.data.next_1_hours,
+            &self.data.next_6_hours,
+            &self.data.next_12_hours,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|period| period.summary.symbol_code.as_str())
+        .next()
+    }
+}
+
+impl Root {
+    /// The forecast from the hour containing `now` on, with the days starting at the midnights of
+    /// `tz`
+    pub fn forecast(&self, tz: &LocalTimeZone, now: OffsetDateTime) -> anyhow::Result<Forecast> {
+        let hour_start = now
+            .replace_minute(0)?
+            .replace_second(0)?
+            .replace_nanosecond(0)?;
+
+        let mut samples = Vec::with_capacity(self.properties.timeseries.len());
+        for ts in &self.properties.timeseries {
+            let time = OffsetDateTime::parse(&ts.time, &Rfc3339)?;
+            let details = &ts.data.instant.details;
+            // the compact format has no apparent temperature, use the air temperature instead
+            let sample = Sample {
+                dt: time.unix_timestamp(),
+                temperature: Temperature::from_celsius(details.air_temperature),
+                feels_like: Temperature::from_celsius(details.air_temperature),
+                conditions: ts.symbol_code().map(conditions).unwrap_or_default(),
+                details: Details {
+                    wind_speed: details.wind_speed.map(|v| v as f32),
+                    wind_gust: None,
+                    wind_direction: details.wind_from_direction.map(|v| v as f32),
+                    humidity: details.relative_humidity.map(|v| v as f32),
+                    pressure: details.air_pressure_at_sea_level.map(|v| v as f32),
+                    dew_point: None,
+                    uv_index: None,
+                    clouds: details.cloud_area_fraction.map(|v| v as f32),
+                    visibility: None,
+                },
+            };
+            samples.push((time, ts.data.next_1_hours.is_some(), sample));
+        }
+
+        // the timeseries is hourly for the first couple of days and coarser afterwards
+        let hourly = samples
+            .iter()
+            .filter(|(time, _, _)| *time >= hour_start)
+            .take_while(|(_, hourly, _)| *hourly)
+            .map(|(_, _, sample)| sample.clone())
+            .collect::<Vec<_>>();
+
+        // the compact format has no daily summary, aggregate the samples of each local day instead
+        let daily = samples
+            .iter()
+            .filter(|(time, _, _)| *time >= hour_start)
+            .map(|(time, _, sample)| (tz.to_local(*time), sample.clone()))
+            .collect::<Vec<_>>();
+
+        let Some(current) = hourly.first().cloned() else {
+            anyhow::bail!("met-no returned an empty forecast");
+        };
+
+        Ok(Forecast {
+            current,
+            hourly,
+            daily: DailySample::aggregate(&daily),
+            alerts: Vec::new(),
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Root {
+    pub properties: Properties,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Properties {
+    pub timeseries: Vec<Timeseries>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timeseries {
+    pub time: String,
+    pub data: Data,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Data {
+    pub instant: Instant,
+    #[serde(rename = "next_1_hours")]
+    pub next_1_hours: Option<Period>,
+    #[serde(rename = "next_6_hours")]
+    pub next_6_hours: Option<Period>,
+    #[serde(rename = "next_12_hours")]
+    pub next_12_hours: Option<Period>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Instant {
+    pub details: InstantDetails,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantDetails {
+    #[serde(rename = "air_temperature")]
+    pub air_temperature: f64,
+    #[serde(rename = "wind_speed")]
+    pub wind_speed: Option<f64>,
+    #[serde(rename = "wind_from_direction")]
+    pub wind_from_direction: Option<f64>,
+    #[serde(rename = "relative_humidity")]
+    pub relative_humidity: Option<f64>,
+    #[serde(rename = "air_pressure_at_sea_level")]
+    pub air_pressure_at_sea_level: Option<f64>,
+    #[serde(rename = "cloud_area_fraction")]
+    pub cloud_area_fraction: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Period {
+    pub summary: Summary,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    #[serde(rename = "symbol_code")]
+    pub symbol_code: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::tests::PRAGUE;
+    use time::macros::datetime;
+
+    fn root() -> Root {
+        serde_json::from_str(include_str!("../tests/fixtures/met-no.json")).unwrap()
+    }
+
+    /// Oslo from 10:00 UTC on 18 March 2026, hourly for two days and six-hourly afterwards,
+    /// converted at 12:20 UTC
+    fn forecast() -> Forecast {
+        let tz = LocalTimeZone::load(Some(PRAGUE)).unwrap();
+        root()
+            .forecast(&tz, datetime!(2026-03-18 12:20 UTC))
+            .unwrap()
+    }
+
+    #[test]
+    fn hourly_from_the_current_hour_to_the_coarser_steps() {
+        let forecast = forecast();
+        assert_eq!(forecast.current.dt, 1773835200);
+        assert_eq!(
+            forecast.current.temperature,
+            Temperature::from_celsius(11.5)
+        );
+        assert_eq!(
+            forecast.current.conditions,
+            Conditions::clouded(Cloudiness::Few)
+        );
+        assert_eq!(forecast.current.details.wind_direction, Some(225.4));
+        assert_eq!(forecast.hourly.len(), 49);
+        assert_eq!(forecast.hourly.last().unwrap().dt, 1774008000);
+        assert!(forecast
+            .hourly
+            .windows(2)
+            .all(|w| w[1].dt == w[0].dt + 3600));
+    }
+
+    #[test]
+    fn daily_grouped_by_local_date() {
+        let daily = forecast().daily;
+        let ranges = daily
+            .iter()
+            .map(|day| (day.temperature.min, day.temperature.max))
+            .collect::<Vec<_>>();
+        let range = |min, max| {
+            (
+                Temperature::from_celsius(min),
+                Temperature::from_celsius(max),
+            )
+        };
+        assert_eq!(
+            ranges,
+            [
+                range(7.5, 12.0),
+                range(3.5, 10.0),
+                range(1.5, 7.5),
+                range(2.5, 8.5)
+            ]
+        );
+        // the 19th starts at the midnight in Oslo
+        assert_eq!(daily[1].dt, 1773874800);
+        assert_eq!(daily[1].conditions.thunder, Some(Intensity::Moderate));
+        assert_eq!(daily[1].conditions.rain, Some(Intensity::Heavy));
+        // the six-hourly steps count towards their days
+        assert_eq!(daily[3].conditions.snow, Some(Intensity::Moderate));
+    }
+
+    #[test]
+    fn empty_forecast() {
+        let tz = LocalTimeZone::load(Some(PRAGUE)).unwrap();
+        let error = root()
+            .forecast(&tz, datetime!(2026-03-22 00:00 UTC))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "met-no returned an empty forecast");
+    }
+
+    #[test]
+    fn symbol_codes() {
+        let precipitation = |rain, snow, showers| Conditions {
+            rain,
+            snow,
+            showers,
+            ..Default::default()
+        };
+        let (light, moderate, heavy) = (
+            Some(Intensity::Light),
+            Some(Intensity::Moderate),
+            Some(Intensity::Heavy),
+        );
+        let table = [
+            ("clearsky_day", Conditions::clouded(Cloudiness::Clear)),
+            ("fair_day", Conditions::clouded(Cloudiness::Few)),
+            (
+                "partlycloudy_day",
+                Conditions::clouded(Cloudiness::Scattered),
+            ),
+            ("cloudy", Conditions::clouded(Cloudiness::Overcast)),
+            (
+                "fog",
+                Conditions {
+                    obscuration: Some(Obscuration::Fog),
+                    ..Default::default()
+                },
+            ),
+            ("lightrain", precipitation(light, None, false)),
+            ("heavyrainshowers_day", precipitation(heavy, None, true)),
+            ("sleet", precipitation(moderate, moderate, false)),
+            ("lightsnowshowers_day", precipitation(None, light, true)),
+            (
+                "snowandthunder",
+                Conditions {
+                    thunder: moderate,
+                    ..precipitation(None, moderate, false)
+                },
+            ),
+        ];
+        for (code, expected) in table {
+            assert_eq!(conditions(code), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn symbol_code_variants() {
+        assert!(!conditions("clearsky_day").night);
+        assert!(conditions("clearsky_night").night);
+        assert!(conditions("fair_polartwilight").night);
+        assert!(!conditions("rain").night);
+        assert_eq!(
+            conditions("fair_polartwilight").clouds,
+            Some(Cloudiness::Few)
+        );
+    }
+
+    #[test]
+    fn legacy_symbol_codes() {
+        let expected = |rain, snow| Conditions {
+            rain,
+            snow,
+            thunder: Some(Intensity::Moderate),
+            showers: true,
+            night: true,
+            ..Default::default()
+        };
+        let light = Some(Intensity::Light);
+        assert_eq!(
+            conditions("lightssleetshowersandthunder_night"),
+            expected(light, light)
+        );
+        assert_eq!(
+            conditions("lightssnowshowersandthunder_night"),
+            expected(None, light)
+        );
+        assert_eq!(
+            conditions("lightsnowshowersandthunder_night"),
+            expected(None, light)
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use crate::condition::{Cloudiness, Conditions, Hazard, Intensity, Obscuration};
+use crate::provider::{FetchFuture, ProviderSettings, WeatherProvider};
+use crate::temperature::Temperature;
+use crate::weather::{DailySample, Details, Forecast, Sample};
+use crate::wind::compass_degrees;
+use serde::Deserialize;
+use serde::Serialize;
+use std::cell::RefCell;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// US National Weather Service provider. Doesn't need an API key, but requires a User-Agent
+/// identifying the application. Only covers the United States.
+pub struct NwsProvider {
+    client: reqwest::Client,
+    points_url: String,
+    /// Hourly forecast URL of the gridpoint covering the location, resolved on first fetch
+    forecast_hourly_url: RefCell<Option<String>>,
+}
+
+impl NwsProvider {
+    pub fn new(settings: &ProviderSettings) -> anyhow::Result<Self> {
+        let ProviderSettings {
+            latitude,
+            longitude,
+            user_agent,
+            ..
+        } = settings;
+        let Some(user_agent) = user_agent else {
+            anyhow::bail!("the nws provider requires a user_agent");
+        };
+        Ok(Self {
+            client: reqwest::Client::builder().user_agent(user_agent).build()?,
+            points_url: format!("https://api.weather.gov/points/{latitude},{longitude}"),
+            forecast_hourly_url: RefCell::new(None),
+        })
+    }
+
+    async fn forecast_hourly_url(&self) -> anyhow::Result<String> {
+        if let Some(url) = &*self.forecast_hourly_url.borrow() {
+            return Ok(url.clone());
+        }
+
+        let points: Points = self
+            .client
+            .get(&self.points_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let url = points.properties.forecast_hourly;
+        self.forecast_hourly_url.replace(Some(url.clone()));
+        Ok(url)
+    }
+
+    async fn fetch_forecast(&self) -> anyhow::Result<Forecast> {
+        let url = self.forecast_hourly_url().await?;
+        let response = self.client.get(&url).send().await?;
+        if response.status().is_client_error() {
+            // the gridpoint may have been remapped, look it up again next time
+            self.forecast_hourly_url.replace(None);
+        }
+        let hourly: HourlyForecast = response.error_for_status()?.json().await?;
+
+        hourly.properties.forecast(OffsetDateTime::now_utc())
+    }
+}
+
+impl WeatherProvider for NwsProvider {
+    fn fetch(&self) -> FetchFuture<'_> {
+        Box::pin(self.fetch_forecast())
+    }
+}
+
+/// Conditions of a short forecast such as `Chance Showers And Thunderstorms`, recognized by its
+/// keywords
+pub fn conditions(short_forecast: &str, is_daytime: bool) -> Conditions {
+    let short_forecast = short_forecast.to_lowercase();
+    let has = |word| short_forecast.contains(word);
+    // whole words only, `slight chance` is about the probability rather than the intensity
+    let has_word = |word| {
+        short_forecast
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|w| w == word)
+    };
+    let intensity = if has_word("light") || has("flurries") {
+        Intensity::Light
+    } else if has("heavy") || has("blizzard") {
+        Intensity::Heavy
+    } else {
+        Intensity::Moderate
+    };
+    let sleet = has("sleet") || has("wintry mix");
+    let rain = sleet || has("rain") || (has("showers") && !has("snow showers"));
+    let snow = sleet || has("snow") || has("flurries") || has("blizzard");
+
+    let obscuration = if has("fog") {
+        Some(Obscuration::Fog)
+    } else if has("smoke") {
+        Some(Obscuration::Smoke)
+    } else if has("dust") {
+        Some(Obscuration::Dust)
+    } else if has("haze") {
+        Some(Obscuration::Haze)
+    } else {
+        None
+    };
+    let clouds = if has("mostly cloudy") {
+        Some(Cloudiness::Broken)
+    } else if has("partly cloudy") || has("partly sunny") {
+        Some(Cloudiness::Scattered)
+    } else if has("mostly sunny") || has("mostly clear") {
+        Some(Cloudiness::Few)
+    } else if has("sunny") || has("clear") {
+        Some(Cloudiness::Clear)
+    } else if has("cloudy") {
+        Some(Cloudiness::Overcast)
+    } else {
+        None
+    };
+
+    Conditions {
+        thunder: has("thunder").then_some(Intensity::Moderate),
+        drizzle: has("drizzle").then_some(intensity),
+        rain: rain.then_some(intensity),
+        freezing: has("freezing"),
+        snow: snow.then_some(intensity),
+        showers: has("showers"),
+        obscuration,
+        clouds,
+        hazard: has("tornado").then_some(Hazard::Tornado),
+        night: !is_daytime,
+    }
+}
+
+/// Speed in m/s of wind such as `10 mph`, or the upper end of a range such as `5 to 10 mph`
+fn wind_speed(s: &str) -> Option<f32> {
+    let mph = s.strip_suffix(" mph")?.split_whitespace().last()?;
+    Some(mph.parse::<f32>().ok()? * 0.44704)
+}
+
+impl Properties {
+    /// The forecast from `now` on, the periods which are over are left out
+    pub fn forecast(&self, now: OffsetDateTime) -> anyhow::Result<Forecast> {
+        let mut hourly = Vec::with_capacity(self.periods.len());
+        for period in &self.periods {
+            let start = OffsetDateTime::parse(&period.start_time, &Rfc3339)?;
+            let end = OffsetDateTime::parse(&period.end_time, &Rfc3339)?;
+            if end <= now {
+                continue;
+            }
+
+            let temperature = match period.temperature_unit.as_str() {
+                "C" => Temperature::from_celsius(period.temperature),
+                _ => Temperature::from_fahrenheit(period.temperature),
+            };
+            // the hourly forecast has no apparent temperature, use the air temperature instead
+            let sample = Sample {
+                dt: start.unix_timestamp(),
+                temperature,
+                feels_like: temperature,
+                conditions: conditions(&period.short_forecast, period.is_daytime),
+                details: Details {
+                    wind_speed: wind_speed(&period.wind_speed),
+                    wind_direction: compass_degrees(&period.wind_direction),
+                    humidity: period.relative_humidity.value.map(|v| v as f32),
+                    dew_point: period.dewpoint.value.map(Temperature::from_celsius),
+                    ..Details::default()
+                },
+            };
+            // periods carry the local offset, so their date is the local date
+            hourly.push((start, sample));
+        }
+
+        let Some((_, current)) = hourly.first().cloned() else {
+            anyhow::bail!("nws returned an empty forecast");
+        };
+
+        Ok(Forecast {
+            current,
+            daily: DailySample::aggregate(&hourly),
+            hourly: hourly.into_iter().map(|(_, sample)| sample).collect(),
+            alerts: Vec::new(),
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Points {
+    pub properties: PointsProperties,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PointsProperties {
+    pub forecast_hourly: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HourlyForecast {
+    pub properties: Properties,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Properties {
+    pub periods: Vec<Period>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Period {
+    pub start_time: String,
+    pub end_time: String,
+    pub temperature: f64,
+    pub temperature_unit: String,
+    pub is_daytime: bool,
+    pub short_forecast: String,
+    #[serde(default)]
+    pub wind_speed: String,
+    #[serde(default)]
+    pub wind_direction: String,
+    #[serde(default)]
+    pub relative_humidity: Measurement,
+    /// Always in degrees Celsius
+    #[serde(default)]
+    pub dewpoint: Measurement,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Measurement {
+    pub value: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    /// Hourly periods of New York from 20:00 on 18 March 2026, converted at 22:30 local time
+    fn forecast() -> Forecast {
+        let hourly: HourlyForecast =
+            serde_json::from_str(include_str!("../tests/fixtures/nws-hourly.json")).unwrap();
+        hourly
+            .properties
+            .forecast(datetime!(2026-03-18 22:30 -4))
+            .unwrap()
+    }
+
+    #[test]
+    fn past_periods_are_dropped() {
+        let forecast = forecast();
+        // the period from 22:00 is still going on
+        assert_eq!(forecast.current.dt, 1773885600);
+        assert_eq!(forecast.hourly[0].dt, 1773885600);
+        assert_eq!(forecast.hourly.len(), 30);
+    }
+
+    #[test]
+    fn current() {
+        let current = forecast().current;
+        assert_eq!(current.temperature, Temperature::from_fahrenheit(46.0));
+        assert_eq!(current.feels_like, current.temperature);
+        assert_eq!(
+            current.conditions,
+            Conditions {
+                night: true,
+                ..Conditions::clouded(Cloudiness::Few)
+            }
+        );
+        assert_eq!(current.details.wind_speed, Some(10.0 * 0.44704));
+        assert_eq!(current.details.wind_direction, Some(315.0));
+        assert_eq!(current.details.humidity, Some(77.0));
+        assert_eq!(
+            current.details.dew_point,
+            Some(Temperature::from_celsius(2.4))
+        );
+    }
+
+    #[test]
+    fn daily_aggregation() {
+        let daily = forecast().daily;
+        assert_eq!(daily.len(), 3);
+        // the rest of the first day
+        assert_eq!(daily[0].temperature.min, Temperature::from_fahrenheit(44.0));
+        assert_eq!(daily[0].temperature.max, Temperature::from_fahrenheit(46.0));
+        // the days start at the local midnight
+        assert_eq!(daily[1].dt, 1773892800);
+        assert_eq!(daily[2].dt, 1773979200);
+        assert_eq!(daily[1].temperature.min, Temperature::from_fahrenheit(30.0));
+        assert_eq!(daily[1].temperature.max, Temperature::from_fahrenheit(60.0));
+        assert_eq!(daily[1].conditions.thunder, Some(Intensity::Moderate));
+        assert_eq!(daily[1].conditions.clouds, Some(Cloudiness::Scattered));
+    }
+
+    #[test]
+    fn empty_forecast() {
+        let hourly: HourlyForecast =
+            serde_json::from_str(include_str!("../tests/fixtures/nws-hourly.json")).unwrap();
+        let error = hourly
+            .properties
+            .forecast(datetime!(2026-03-21 00:00 UTC))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "nws returned an empty forecast");
+    }
+
+    #[test]
+    fn short_forecasts() {
+        let showers = conditions("Slight Chance Rain Showers", true);
+        // slight is about the probability
+        assert_eq!(showers.rain, Some(Intensity::Moderate));
+        assert!(showers.showers);
+        assert_eq!(showers.snow, None);
+        assert_eq!(showers.clouds, None);
+
+        let snow_showers = conditions("Snow Showers", true);
+        assert_eq!(snow_showers.snow, Some(Intensity::Moderate));
+        assert_eq!(snow_showers.rain, None);
+        assert!(snow_showers.showers);
+
+        let wintry_mix = conditions("Wintry Mix", false);
+        assert_eq!(wintry_mix.rain, Some(Intensity::Moderate));
+        assert_eq!(wintry_mix.snow, Some(Intensity::Moderate));
+        assert!(wintry_mix.night);
+
+        assert_eq!(
+            conditions("Mostly Sunny", true),
+            Conditions::clouded(Cloudiness::Few)
+        );
+        assert_eq!(conditions("Light Rain", true).rain, Some(Intensity::Light));
+        assert_eq!(
+            conditions("Patchy Fog", true).obscuration,
+            Some(Obscuration::Fog)
+        );
+    }
+
+    #[test]
+    fn wind_speeds() {
+        assert_eq!(wind_speed("10 mph"), Some(10.0 * 0.44704));
+        assert_eq!(wind_speed("5 to 10 mph"), Some(10.0 * 0.44704));
+        assert_eq!(wind_speed("0 mph"), Some(0.0));
+        assert_eq!(wind_speed(""), None);
+        assert_eq!(wind_speed("calm"), None);
+    }
+}
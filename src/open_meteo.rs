@@ -0,0 +1,350 @@
+use crate::condition::{Cloudiness, Conditions, Intensity, Obscuration};
+use crate::provider::{FetchFuture, ProviderSettings, WeatherProvider};
+use crate::temperature::Temperature;
+use crate::weather::{DailySample, DailyTemperatures, Details, Forecast, Sample};
+use serde::Deserialize;
+use serde::Serialize;
+
+const CURRENT: &str = "temperature_2m,apparent_temperature,weather_code,is_day,wind_speed_10m,wind_gusts_10m,wind_direction_10m,relative_humidity_2m,pressure_msl,dew_point_2m,uv_index,cloud_cover,visibility";
+const HOURLY: &str = "temperature_2m,apparent_temperature,weather_code,is_day";
+const DAILY: &str =
+    "temperature_2m_min,temperature_2m_max,apparent_temperature_min,apparent_temperature_max,weather_code,sunrise,sunset";
+
+pub async fn fetch_forecast(url: &str) -> anyhow::Result<Root> {
+    Ok(reqwest::get(url).await?.json().await?)
+}
+
+/// Open-Meteo forecast provider. Doesn't need an API key.
+pub struct OpenMeteoProvider {
+    url: String,
+}
+
+impl OpenMeteoProvider {
+    pub fn new(settings: &ProviderSettings) -> Self {
+        let ProviderSettings {
+            latitude,
+            longitude,
+            ..
+        } = settings;
+        Self {
+            url: format!("https://api.open-meteo.com/v1/forecast?latitude={latitude}&longitude={longitude}&current={CURRENT}&hourly={HOURLY}&daily={DAILY}&temperature_unit=celsius&wind_speed_unit=ms&timeformat=unixtime&timezone=auto"),
+        }
+    }
+}
+
+impl WeatherProvider for OpenMeteoProvider {
+    fn fetch(&self) -> FetchFuture<'_> {
+        Box::pin(async move { Forecast::try_from(fetch_forecast(&self.url).await?) })
+    }
+}
+
+/// Conditions of a WMO weather interpretation code. Unknown codes have no conditions.
+pub fn conditions(weather_code: u8) -> Conditions {
+    use Intensity::*;
+
+    let none = Conditions::default();
+    match weather_code {
+        0 => Conditions::clouded(Cloudiness::Clear),
+        1 => Conditions::clouded(Cloudiness::Few),
+        2 => Conditions::clouded(Cloudiness::Scattered),
+        3 => Conditions::clouded(Cloudiness::Overcast),
+        // fog and depositing rime fog
+        45 | 48 => Conditions::obscured(Obscuration::Fog),
+        51 | 53 | 55 => Conditions {
+            drizzle: Some(intensity(weather_code, 51)),
+            ..none
+        },
+        56 | 57 => Conditions {
+            drizzle: Some(if weather_code == 56 { Light } else { Heavy }),
+            freezing: true,
+            ..none
+        },
+        61 | 63 | 65 => Conditions {
+            rain: Some(intensity(weather_code, 61)),
+            ..none
+        },
+        66 | 67 => Conditions {
+            rain: Some(if weather_code == 66 { Light } else { Heavy }),
+            freezing: true,
+            ..none
+        },
+        71 | 73 | 75 => Conditions {
+            snow: Some(intensity(weather_code, 71)),
+            ..none
+        },
+        // snow grains
+        77 => Conditions {
+            snow: Some(Light),
+            ..none
+        },
+        80 => Conditions {
+            rain: Some(Light),
+            showers: true,
+            ..none
+        },
+        81 => Conditions {
+            rain: Some(Moderate),
+            showers: true,
+            ..none
+        },
+        82 => Conditions {
+            rain: Some(Extreme),
+            showers: true,
+            ..none
+        },
+        85 | 86 => Conditions {
+            snow: Some(if weather_code == 85 { Light } else { Heavy }),
+            showers: true,
+            ..none
+        },
+        // thunderstorm, optionally with slight or heavy hail
+        95 | 96 => Conditions {
+            thunder: Some(Moderate),
+            ..none
+        },
+        99 => Conditions {
+            thunder: Some(Heavy),
+            ..none
+        },
+        _ => none,
+    }
+}
+
+/// Intensity of the slight, moderate and heavy codes following each other by two from `light`
+fn intensity(weather_code: u8, light: u8) -> Intensity {
+    match weather_code - light {
+        0 => Intensity::Light,
+        2 => Intensity::Moderate,
+        _ => Intensity::Heavy,
+    }
+}
+
+impl TryFrom<Root> for Forecast {
+    type Error = anyhow::Error;
+
+    fn try_from(om: Root) -> anyhow::Result<Self> {
+        let c = &om.current;
+        let current = Sample {
+            dt: c.time,
+            temperature: Temperature::from_celsius(c.temperature_2m),
+            feels_like: Temperature::from_celsius(c.apparent_temperature),
+            conditions: Conditions {
+                night: c.is_day == 0,
+                ..conditions(c.weather_code)
+            },
+            details: Details {
+                wind_speed: c.wind_speed_10m.map(|v| v as f32),
+                wind_gust: c.wind_gusts_10m.map(|v| v as f32),
+                wind_direction: c.wind_direction_10m.map(|v| v as f32),
+                humidity: c.relative_humidity_2m.map(|v| v as f32),
+                pressure: c.pressure_msl.map(|v| v as f32),
+                dew_point: c.dew_point_2m.map(Temperature::from_celsius),
+                uv_index: c.uv_index.map(|v| v as f32),
+                clouds: c.cloud_cover.map(|v| v as f32),
+                visibility: c.visibility.map(|v| v as f32),
+            },
+        };
+
+        // hourly data starts at midnight, skip the hours which have already passed
+        let hour_start = om.current.time - om.current.time.rem_euclid(3600);
+        let h = &om.hourly;
+        let hourly = (0..h.time.len())
+            .filter(|i| h.time[*i] >= hour_start)
+            .map_while(|i| {
+                Some(Sample {
+                    dt: h.time[i],
+                    temperature: Temperature::from_celsius((*h.temperature_2m.get(i)?)?),
+                    feels_like: Temperature::from_celsius((*h.apparent_temperature.get(i)?)?),
+                    conditions: Conditions {
+                        night: (*h.is_day.get(i)?)? == 0,
+                        ..conditions((*h.weather_code.get(i)?)?)
+                    },
+                    // the details are only requested for the current weather
+                    details: Details::default(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // with the timezone set, days start at the local midnight
+        let d = &om.daily;
+        let daily = (0..d.time.len())
+            .map_while(|i| {
+                Some(DailySample {
+                    dt: d.time[i],
+                    temperature: DailyTemperatures::from_range(
+                        Temperature::from_celsius((*d.temperature_2m_min.get(i)?)?),
+                        Temperature::from_celsius((*d.temperature_2m_max.get(i)?)?),
+                    ),
+                    feels_like: DailyTemperatures::from_range(
+                        Temperature::from_celsius((*d.apparent_temperature_min.get(i)?)?),
+                        Temperature::from_celsius((*d.apparent_temperature_max.get(i)?)?),
+                    ),
+                    conditions: conditions((*d.weather_code.get(i)?)?),
+                    // there's no sunrise or sunset during polar days and nights
+                    sunrise: d.sunrise.get(i).copied().flatten(),
+                    sunset: d.sunset.get(i).copied().flatten(),
+                    details: Details::default(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if hourly.is_empty() || daily.is_empty() {
+            anyhow::bail!("open-meteo returned an empty forecast");
+        }
+
+        Ok(Self {
+            current,
+            hourly,
+            daily,
+            alerts: Vec::new(),
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Root {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub timezone: String,
+    #[serde(rename = "utc_offset_seconds")]
+    pub utc_offset_seconds: i64,
+    pub current: Current,
+    pub hourly: Hourly,
+    pub daily: Daily,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Current {
+    pub time: i64,
+    pub interval: i64,
+    #[serde(rename = "temperature_2m")]
+    pub temperature_2m: f64,
+    #[serde(rename = "apparent_temperature")]
+    pub apparent_temperature: f64,
+    #[serde(rename = "weather_code")]
+    pub weather_code: u8,
+    #[serde(rename = "is_day")]
+    pub is_day: u8,
+    #[serde(rename = "wind_speed_10m")]
+    pub wind_speed_10m: Option<f64>,
+    #[serde(rename = "wind_gusts_10m")]
+    pub wind_gusts_10m: Option<f64>,
+    #[serde(rename = "wind_direction_10m")]
+    pub wind_direction_10m: Option<f64>,
+    #[serde(rename = "relative_humidity_2m")]
+    pub relative_humidity_2m: Option<f64>,
+    #[serde(rename = "pressure_msl")]
+    pub pressure_msl: Option<f64>,
+    #[serde(rename = "dew_point_2m")]
+    pub dew_point_2m: Option<f64>,
+    #[serde(rename = "uv_index")]
+    pub uv_index: Option<f64>,
+    #[serde(rename = "cloud_cover")]
+    pub cloud_cover: Option<f64>,
+    pub visibility: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Hourly {
+    pub time: Vec<i64>,
+    #[serde(rename = "temperature_2m")]
+    pub temperature_2m: Vec<Option<f64>>,
+    #[serde(rename = "apparent_temperature")]
+    pub apparent_temperature: Vec<Option<f64>>,
+    #[serde(rename = "weather_code")]
+    pub weather_code: Vec<Option<u8>>,
+    #[serde(rename = "is_day")]
+    pub is_day: Vec<Option<u8>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Daily {
+    pub time: Vec<i64>,
+    #[serde(rename = "temperature_2m_min")]
+    pub temperature_2m_min: Vec<Option<f64>>,
+    #[serde(rename = "temperature_2m_max")]
+    pub temperature_2m_max: Vec<Option<f64>>,
+    #[serde(rename = "apparent_temperature_min")]
+    pub apparent_temperature_min: Vec<Option<f64>>,
+    #[serde(rename = "apparent_temperature_max")]
+    pub apparent_temperature_max: Vec<Option<f64>>,
+    #[serde(rename = "weather_code")]
+    pub weather_code: Vec<Option<u8>>,
+    pub sunrise: Vec<Option<i64>>,
+    pub sunset: Vec<Option<i64>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tromsø during the polar day, the last hours and day of the forecast are missing
+    fn forecast() -> Forecast {
+        let root: Root =
+            serde_json::from_str(include_str!("../tests/fixtures/open-meteo.json")).unwrap();
+        Forecast::try_from(root).unwrap()
+    }
+
+    #[test]
+    fn current() {
+        let current = forecast().current;
+        assert_eq!(current.dt, 1718957700);
+        assert_eq!(current.temperature, Temperature::from_celsius(12.6));
+        assert_eq!(current.feels_like, Temperature::from_celsius(10.9));
+        assert_eq!(
+            current.conditions,
+            Conditions::clouded(Cloudiness::Scattered)
+        );
+        assert_eq!(current.details.wind_speed, Some(4.3));
+        assert_eq!(current.details.wind_direction, Some(338.0));
+        assert_eq!(current.details.visibility, Some(24140.0));
+    }
+
+    #[test]
+    fn hourly_starts_at_the_current_hour() {
+        let hourly = forecast().hourly;
+        assert_eq!(hourly[0].dt, 1718956800);
+        assert_eq!(hourly[0].temperature, Temperature::from_celsius(12.9));
+        assert!(hourly.windows(2).all(|w| w[1].dt == w[0].dt + 3600));
+    }
+
+    #[test]
+    fn hourly_stops_at_missing_values() {
+        let hourly = forecast().hourly;
+        assert_eq!(hourly.len(), 10);
+        assert_eq!(hourly.last().unwrap().dt, 1718989200);
+    }
+
+    #[test]
+    fn weather_codes() {
+        let hourly = forecast().hourly;
+        assert_eq!(
+            hourly[0].conditions,
+            Conditions::clouded(Cloudiness::Scattered)
+        );
+        assert_eq!(hourly[1].conditions.rain, Some(Intensity::Light));
+        assert_eq!(hourly[2].conditions.thunder, Some(Intensity::Moderate));
+        assert_eq!(hourly[4].conditions.rain, Some(Intensity::Light));
+        assert!(hourly[4].conditions.showers);
+        assert!(hourly.iter().all(|h| !h.conditions.night));
+    }
+
+    #[test]
+    fn daily_during_the_polar_day() {
+        let daily = forecast().daily;
+        // the last day has no values
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].dt, 1718920800);
+        assert_eq!(daily[0].temperature.min, Temperature::from_celsius(8.8));
+        assert_eq!(daily[0].temperature.max, Temperature::from_celsius(14.5));
+        assert_eq!(daily[0].conditions.thunder, Some(Intensity::Moderate));
+        assert_eq!(daily[1].conditions.snow, Some(Intensity::Moderate));
+        assert!(daily
+            .iter()
+            .all(|d| d.sunrise.is_none() && d.sunset.is_none()));
+    }
+}
@@ -1,3 +1,9 @@
+use crate::condition::Conditions;
+use crate::provider::{FetchFuture, ProviderSettings, WeatherProvider};
+use crate::temperature::Temperature;
+use crate::weather::{
+    Alert as ForecastAlert, DailySample, DailyTemperatures, Details, Forecast, Sample,
+};
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -5,6 +11,135 @@ pub async fn fetch_current_weather(url: &str) -> anyhow::Result<Root> {
     Ok(reqwest::get(url).await?.json().await?)
 }
 
+/// OpenWeatherMap One Call 3.0 provider
+pub struct OwmProvider {
+    url: String,
+}
+
+impl OwmProvider {
+    pub fn new(settings: &ProviderSettings) -> anyhow::Result<Self> {
+        let ProviderSettings {
+            latitude,
+            longitude,
+            appid,
+            locale,
+            ..
+        } = settings;
+        let Some(appid) = appid else {
+            anyhow::bail!("the owm provider requires an appid");
+        };
+        let lang = locale.owm_lang();
+        Ok(Self {
+            url: format!("https://api.openweathermap.org/data/3.0/onecall?units=metric&lang={lang}&lat={latitude}&lon={longitude}&appid={appid}"),
+        })
+    }
+}
+
+impl WeatherProvider for OwmProvider {
+    fn fetch(&self) -> FetchFuture<'_> {
+        Box::pin(async move { Ok(fetch_current_weather(&self.url).await?.into()) })
+    }
+}
+
+impl From<Root> for Forecast {
+    fn from(owm: Root) -> Self {
+        let c = &owm.current;
+        let current = Sample {
+            dt: c.dt,
+            temperature: Temperature::from_celsius(c.temp),
+            feels_like: Temperature::from_celsius(c.feels_like),
+            conditions: Conditions::from(c.weather.iter()),
+            details: Details {
+                wind_speed: Some(c.wind_speed as f32),
+                wind_gust: c.wind_gust.map(|gust| gust as f32),
+                wind_direction: Some(c.wind_deg as f32),
+                humidity: Some(c.humidity as f32),
+                pressure: Some(c.pressure as f32),
+                dew_point: Some(Temperature::from_celsius(c.dew_point)),
+                uv_index: Some(c.uvi as f32),
+                clouds: Some(c.clouds as f32),
+                visibility: Some(c.visibility as f32),
+            },
+        };
+
+        let hourly = owm
+            .hourly
+            .iter()
+            .map(|h| Sample {
+                dt: h.dt,
+                temperature: Temperature::from_celsius(h.temp),
+                feels_like: Temperature::from_celsius(h.feels_like),
+                conditions: Conditions::from(h.weather.iter()),
+                details: Details {
+                    wind_speed: Some(h.wind_speed as f32),
+                    wind_gust: Some(h.wind_gust as f32),
+                    wind_direction: Some(h.wind_deg as f32),
+                    humidity: Some(h.humidity as f32),
+                    pressure: Some(h.pressure as f32),
+                    dew_point: Some(Temperature::from_celsius(h.dew_point)),
+                    uv_index: Some(h.uvi as f32),
+                    clouds: Some(h.clouds as f32),
+                    visibility: Some(h.visibility as f32),
+                },
+            })
+            .collect();
+
+        let daily = owm
+            .daily
+            .iter()
+            .map(|d| DailySample {
+                // daily timestamps point to the local noon
+                dt: d.dt - (d.dt + owm.timezone_offset).rem_euclid(86400),
+                temperature: DailyTemperatures {
+                    min: Temperature::from_celsius(d.temp.min),
+                    max: Temperature::from_celsius(d.temp.max),
+                    day: Temperature::from_celsius(d.temp.day),
+                    night: Temperature::from_celsius(d.temp.night),
+                },
+                // there's no feels-like minimum and maximum, use the extremes of the day parts
+                feels_like: DailyTemperatures {
+                    min: Temperature::from_celsius(d.feels_like.min()),
+                    max: Temperature::from_celsius(d.feels_like.max()),
+                    day: Temperature::from_celsius(d.feels_like.day),
+                    night: Temperature::from_celsius(d.feels_like.night),
+                },
+                conditions: Conditions::from(d.weather.iter()),
+                sunrise: Some(d.sunrise),
+                sunset: Some(d.sunset),
+                details: Details {
+                    wind_speed: Some(d.wind_speed as f32),
+                    wind_gust: Some(d.wind_gust as f32),
+                    wind_direction: Some(d.wind_deg as f32),
+                    humidity: Some(d.humidity as f32),
+                    pressure: Some(d.pressure as f32),
+                    dew_point: Some(Temperature::from_celsius(d.dew_point)),
+                    uv_index: Some(d.uvi as f32),
+                    clouds: Some(d.clouds as f32),
+                    // daily forecasts have no visibility
+                    visibility: None,
+                },
+            })
+            .collect();
+
+        let alerts = owm
+            .alerts
+            .iter()
+            .map(|a| ForecastAlert {
+                event: a.event.clone(),
+                start: a.start,
+                end: a.end,
+            })
+            .collect();
+
+        Self {
+            current,
+            hourly,
+            daily,
+            alerts,
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Root {
@@ -41,6 +176,9 @@ pub struct Current {
     pub wind_speed: f64,
     #[serde(rename = "wind_deg")]
     pub wind_deg: i64,
+    /// Only reported when there are gusts
+    #[serde(rename = "wind_gust")]
+    pub wind_gust: Option<f64>,
     pub weather: Vec<Weather>,
 }
 
@@ -135,6 +273,16 @@ pub struct FeelsLike {
     pub morn: f64,
 }
 
+impl FeelsLike {
+    fn min(&self) -> f64 {
+        self.day.min(self.night).min(self.eve).min(self.morn)
+    }
+
+    fn max(&self) -> f64 {
+        self.day.max(self.night).max(self.eve).max(self.morn)
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Alert {
@@ -0,0 +1,259 @@
+use crate::condition::Conditions;
+use crate::owm::Weather;
+use crate::provider::{FetchFuture, ProviderSettings, WeatherProvider};
+use crate::temperature::Temperature;
+use crate::timezone::LocalTimeZone;
+use crate::weather::{DailySample, Details, Forecast, Sample};
+use serde::Deserialize;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+/// OpenWeatherMap provider using the 2.5 current weather and 5 day / 3 hour forecast endpoints,
+/// which are available with the free plan
+pub struct Owm25Provider {
+    current_url: String,
+    forecast_url: String,
+    timezone: LocalTimeZone,
+}
+
+impl Owm25Provider {
+    pub fn new(settings: &ProviderSettings) -> anyhow::Result<Self> {
+        let ProviderSettings {
+            latitude,
+            longitude,
+            appid,
+            locale,
+            timezone,
+            ..
+        } = settings;
+        let Some(appid) = appid else {
+            anyhow::bail!("the owm-2.5 provider requires an appid");
+        };
+        let lang = locale.owm_lang();
+        let query =
+            format!("units=metric&lang={lang}&lat={latitude}&lon={longitude}&appid={appid}");
+        Ok(Self {
+            current_url: format!("https://api.openweathermap.org/data/2.5/weather?{query}"),
+            forecast_url: format!("https://api.openweathermap.org/data/2.5/forecast?{query}"),
+            timezone: timezone.clone(),
+        })
+    }
+
+    async fn fetch_forecast(&self) -> anyhow::Result<Forecast> {
+        let current: CurrentRoot = reqwest::get(&self.current_url).await?.json().await?;
+        let forecast: ForecastRoot = reqwest::get(&self.forecast_url).await?.json().await?;
+        forecast.forecast(&current, &self.timezone)
+    }
+}
+
+impl WeatherProvider for Owm25Provider {
+    fn fetch(&self) -> FetchFuture<'_> {
+        Box::pin(self.fetch_forecast())
+    }
+}
+
+impl ForecastRoot {
+    /// The forecast from the `current` weather on, with the days starting at the midnights of `tz`
+    pub fn forecast(&self, current: &CurrentRoot, tz: &LocalTimeZone) -> anyhow::Result<Forecast> {
+        let Some(last) = self.list.last() else {
+            anyhow::bail!("owm-2.5 returned an empty forecast");
+        };
+
+        let sample = |dt, entry: &ForecastEntry| Sample {
+            dt,
+            temperature: Temperature::from_celsius(entry.main.temp),
+            feels_like: Temperature::from_celsius(entry.main.feels_like),
+            conditions: Conditions::from(entry.weather.iter()),
+            details: Details {
+                wind_speed: Some(entry.wind.speed as f32),
+                wind_gust: entry.wind.gust.map(|gust| gust as f32),
+                wind_direction: Some(entry.wind.deg as f32),
+                humidity: Some(entry.main.humidity as f32),
+                pressure: Some(entry.main.pressure as f32),
+                dew_point: None,
+                uv_index: None,
+                clouds: Some(entry.clouds.all as f32),
+                visibility: entry.visibility.map(|visibility| visibility as f32),
+            },
+        };
+
+        // there is one entry per 3 hours, fill each hour with the closest one
+        let hour_start = current.entry.dt - current.entry.dt.rem_euclid(3600);
+        let hours = (last.dt - hour_start) / 3600;
+        let mut hourly = vec![sample(hour_start, &current.entry)];
+        for hour in 1..=hours {
+            let dt = hour_start + hour * 3600;
+            let closest = self
+                .list
+                .iter()
+                .min_by_key(|entry| (entry.dt - dt).abs())
+                .unwrap_or(last);
+            hourly.push(sample(dt, closest));
+        }
+
+        // group the 3 hour entries of each local day, today starts with the current weather. The
+        // city's offset is the one at the time of the request, so it's off after the clocks change.
+        let local = |dt| -> anyhow::Result<OffsetDateTime> {
+            Ok(tz.to_local(OffsetDateTime::from_unix_timestamp(dt)?))
+        };
+        let mut samples = vec![(
+            local(current.entry.dt)?,
+            sample(current.entry.dt, &current.entry),
+        )];
+        for entry in self.list.iter().filter(|entry| entry.dt > current.entry.dt) {
+            samples.push((local(entry.dt)?, sample(entry.dt, entry)));
+        }
+
+        Ok(Forecast {
+            current: sample(current.entry.dt, &current.entry),
+            hourly,
+            daily: DailySample::aggregate(&samples),
+            alerts: Vec::new(),
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentRoot {
+    pub timezone: i32,
+    /// The current weather has the same fields as the forecast entries
+    #[serde(flatten)]
+    pub entry: ForecastEntry,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Main {
+    pub temp: f64,
+    #[serde(rename = "feels_like")]
+    pub feels_like: f64,
+    #[serde(rename = "temp_min")]
+    pub temp_min: f64,
+    #[serde(rename = "temp_max")]
+    pub temp_max: f64,
+    pub pressure: i64,
+    pub humidity: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastRoot {
+    pub list: Vec<ForecastEntry>,
+    pub city: City,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastEntry {
+    pub dt: i64,
+    pub main: Main,
+    pub weather: Vec<Weather>,
+    #[serde(default)]
+    pub wind: Wind,
+    #[serde(default)]
+    pub clouds: Clouds,
+    /// Visibility in meters, missing when it isn't known
+    pub visibility: Option<i64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Wind {
+    pub speed: f64,
+    pub deg: i64,
+    pub gust: Option<f64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Clouds {
+    pub all: i64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct City {
+    pub name: String,
+    pub timezone: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::condition::Intensity;
+    use crate::source::tests::PRAGUE;
+
+    /// Prague at 10:10 UTC on 27 March 2026 and the 3 hour entries of the five days after, across
+    /// the change to the summer time on the 29th
+    fn forecast() -> Forecast {
+        let current: CurrentRoot =
+            serde_json::from_str(include_str!("../tests/fixtures/owm25-current.json")).unwrap();
+        let forecast: ForecastRoot =
+            serde_json::from_str(include_str!("../tests/fixtures/owm25-forecast.json")).unwrap();
+        let tz = LocalTimeZone::load(Some(PRAGUE)).unwrap();
+        forecast.forecast(&current, &tz).unwrap()
+    }
+
+    #[test]
+    fn hours_filled_from_the_closest_entry() {
+        let forecast = forecast();
+        let temperatures = forecast
+            .hourly
+            .iter()
+            .take(5)
+            .map(|sample| sample.temperature)
+            .collect::<Vec<_>>();
+        // the current weather, three hours of the 12:00 entry and the 15:00 one
+        let expected = [9.4, 10.3, 10.3, 10.3, 10.8].map(Temperature::from_celsius);
+        assert_eq!(temperatures, expected);
+        assert_eq!(forecast.hourly[0].dt, 1774605600);
+        assert_eq!(forecast.current.dt, 1774606200);
+        assert_eq!(forecast.hourly.len(), 120);
+        assert_eq!(forecast.hourly.last().unwrap().dt, 1775034000);
+        assert!(forecast
+            .hourly
+            .windows(2)
+            .all(|w| w[1].dt == w[0].dt + 3600));
+    }
+
+    #[test]
+    fn daily_grouped_by_local_date() {
+        let daily = forecast().daily;
+        let ranges = daily
+            .iter()
+            .map(|day| (day.temperature.min, day.temperature.max))
+            .collect::<Vec<_>>();
+        let range = |min, max| {
+            (
+                Temperature::from_celsius(min),
+                Temperature::from_celsius(max),
+            )
+        };
+        assert_eq!(
+            ranges,
+            [
+                range(4.7, 10.8),
+                range(0.7, 10.3),
+                range(0.7, 9.8),
+                range(-0.3, 9.3),
+                range(-0.8, 8.8),
+                range(-1.3, 6.0),
+            ]
+        );
+        // tomorrow starts at the midnight in winter time, the 30th at the one in summer time
+        assert_eq!(daily[1].dt, 1774652400);
+        assert_eq!(daily[3].dt, 1774821600);
+        assert_eq!(daily[1].conditions.rain, Some(Intensity::Moderate));
+        assert_eq!(daily[2].conditions.snow, Some(Intensity::Moderate));
+    }
+
+    #[test]
+    fn empty_forecast() {
+        let tz = LocalTimeZone::load(Some(PRAGUE)).unwrap();
+        let error = ForecastRoot::default()
+            .forecast(&CurrentRoot::default(), &tz)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "owm-2.5 returned an empty forecast");
+    }
+}
@@ -0,0 +1,304 @@
+use crate::condition::{Cloudiness, Conditions};
+use crate::geometry::Geometry;
+use crate::icon::Icon;
+use crate::source::Settings;
+use crate::temperature::{Temperature, Units};
+use crate::template::Template;
+use crate::time_format::TimeFormat;
+use crate::weather::{DailySample, Forecast};
+use crate::Text;
+use std::fmt::Debug;
+use std::time::Duration;
+use time::format_description::FormatItem;
+use time::OffsetDateTime;
+
+/// Number of columns the pages are designed for
+pub const PAGE_WIDTH: usize = 20;
+
+/// How long a page stays on the display unless configured otherwise
+const DEFAULT_DWELL: Duration = Duration::from_secs(15);
+
+/// How trustworthy the displayed weather data is
+pub enum Status {
+    NoData,
+    Stale { since: OffsetDateTime },
+    Fresh,
+}
+
+/// Everything a page is rendered from
+pub struct PageContext<'a> {
+    /// Current local time
+    pub now: OffsetDateTime,
+    pub forecast: &'a Forecast,
+    pub status: Status,
+    /// Error of the last fetch, if it failed
+    pub error: Option<&'a str>,
+    pub settings: &'a Settings,
+    /// Advances every few seconds, to cycle through values which don't fit at once
+    pub frame: u64,
+}
+
+impl<'a> PageContext<'a> {
+    /// Toggled with every frame to alternate between two values
+    pub fn alternate(&self) -> bool {
+        self.frame % 2 == 1
+    }
+
+    /// The forecast of the current local day
+    pub fn today(&self) -> Option<&'a DailySample> {
+        self.forecast.date(self.now.date(), &self.settings.timezone)
+    }
+
+    /// Number of cells a row of the page can take on the configured display
+    pub fn row_width(&self) -> usize {
+        let geometry = self.settings.geometry;
+        Arrangement::of(geometry).row_width(geometry)
+    }
+}
+
+/// One screen of the rotation
+pub trait Page: Debug {
+    /// Render the page, or `None` if it has nothing to show and should be skipped
+    fn render(&self, ctx: &PageContext) -> Option<Text>;
+}
+
+/// A page together with how long it's shown
+#[derive(Debug)]
+pub struct PageSpec {
+    pub page: Box<dyn Page>,
+    pub dwell: Duration,
+}
+
+impl PageSpec {
+    /// Parse `page` or `page:seconds`, the custom page is laid out by `layout`
+    fn parse(s: &str, layout: Option<&Template>) -> anyhow::Result<Self> {
+        let (name, dwell) = match s.split_once(':') {
+            Some((name, seconds)) => (name.trim(), Duration::from_secs(seconds.trim().parse()?)),
+            None => (s.trim(), DEFAULT_DWELL),
+        };
+        if dwell.is_zero() {
+            anyhow::bail!("page {name} must be shown for at least a second");
+        }
+
+        let page: Box<dyn Page> = match name {
+            "forecast" => Box::new(crate::forecast_page::ForecastPage),
+            "today" => Box::new(crate::today_page::TodayPage),
+            "sun" => Box::new(crate::sun_page::SunPage),
+            "alerts" => Box::new(crate::alert_page::AlertPage),
+            "details" => Box::new(crate::details_page::DetailsPage),
+            "custom" => match layout {
+                Some(template) => Box::new(crate::layout_page::LayoutPage::new(template.clone())),
+                None => anyhow::bail!("page custom needs a layout"),
+            },
+            _ => anyhow::bail!(
+                "unknown page {name}, expected forecast, today, sun, alerts, details or custom"
+            ),
+        };
+        Ok(Self { page, dwell })
+    }
+}
+
+/// Parse a comma-separated list of pages, such as `forecast:30,today:10,alerts`
+pub fn parse_pages(s: &str, layout: Option<&Template>) -> anyhow::Result<Vec<PageSpec>> {
+    let pages = s
+        .split(',')
+        .map(|page| PageSpec::parse(page, layout))
+        .collect::<anyhow::Result<Vec<PageSpec>>>()?;
+    if pages.is_empty() {
+        anyhow::bail!("at least one page has to be shown");
+    }
+    Ok(pages)
+}
+
+/// How the pages, designed for 20x4 displays, are arranged on the configured display
+#[derive(Clone, Copy, PartialEq)]
+pub enum Arrangement {
+    /// One row of the page per row of the display
+    Rows,
+    /// Two rows of the page side by side in every row of the display, a column apart
+    SideBySide,
+    /// The first row of the page with the others cycling below it
+    Cycling,
+}
+
+impl Arrangement {
+    pub fn of(geometry: Geometry) -> Self {
+        if geometry.rows >= 4 {
+            Arrangement::Rows
+        } else if geometry.cols >= 2 * PAGE_WIDTH {
+            Arrangement::SideBySide
+        } else {
+            Arrangement::Cycling
+        }
+    }
+
+    /// Number of cells a row of the page can take
+    pub fn row_width(&self, geometry: Geometry) -> usize {
+        match self {
+            Arrangement::SideBySide => geometry.cols / 2 - 1,
+            _ => geometry.cols,
+        }
+    }
+}
+
+/// Put the rows of a page on the display according to its arrangement
+pub fn arrange(ctx: &PageContext, rows: &[String]) -> Text {
+    let geometry = ctx.settings.geometry;
+    let mut text = Text::new(geometry);
+    match Arrangement::of(geometry) {
+        Arrangement::Rows => {
+            for (i, row) in rows.iter().enumerate() {
+                text.set_row(i, row);
+            }
+        }
+        Arrangement::SideBySide => {
+            let width = ctx.row_width();
+            for (i, pair) in rows.chunks(2).enumerate() {
+                let left = crate::icon::pad(&pair[0], width);
+                let right = pair.get(1).map_or(String::new(), |right| {
+                    crate::icon::truncate(right, width).to_string()
+                });
+                text.set_row(i, &format!("{left} {right}"));
+            }
+        }
+        Arrangement::Cycling => {
+            if let Some((first, others)) = rows.split_first() {
+                text.set_row(0, first);
+                if !others.is_empty() {
+                    let shown = ctx.frame as usize % others.len();
+                    text.set_row(1, &others[shown]);
+                }
+            }
+        }
+    }
+    text
+}
+
+/// The first of `candidates`, ordered from the most to the least verbose, which fits a row of the
+/// page. The last one is cut off if none of them does.
+pub fn fit(ctx: &PageContext, candidates: &[String]) -> String {
+    let width = ctx.row_width();
+    let row = candidates
+        .iter()
+        .find(|row| crate::icon::width(row) <= width)
+        .or(candidates.last())
+        .map_or("", String::as_str);
+    crate::icon::truncate(row, width).to_string()
+}
+
+lazy_static::lazy_static! {
+    pub static ref TIME_FORMAT: &'static [FormatItem<'static>] = time::macros::format_description!(
+        "[hour]:[minute]"
+    );
+}
+
+/// Character telling whether the weather data can be trusted
+pub fn status_indicator(ctx: &PageContext) -> char {
+    match (ctx.error, &ctx.status) {
+        (Some(_), _) => '!',
+        (None, Status::NoData) => '?',
+        (None, Status::Stale { .. }) => '~',
+        (None, Status::Fresh) => ' ',
+    }
+}
+
+/// Number of characters the clock can take, the last column of its row is the status indicator
+fn clock_width(geometry: Geometry) -> usize {
+    Arrangement::of(geometry).row_width(geometry) - 1
+}
+
+/// The configured clock format if it fits the display, otherwise an error. Unless configured,
+/// the month is shown if there's room for it.
+pub fn clock_format(
+    configured: Option<TimeFormat>,
+    geometry: Geometry,
+) -> anyhow::Result<TimeFormat> {
+    let width = clock_width(geometry);
+    let format = match configured {
+        Some(format) => format,
+        None => ["%a %b %d %H:%M:%S", "%a %d %H:%M:%S"]
+            .into_iter()
+            .map(|format| format.parse::<TimeFormat>())
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into_iter()
+            .find(|format| format.width() <= width)
+            .ok_or_else(|| anyhow::anyhow!("no clock format fits {width} characters"))?,
+    };
+    if format.width() > width {
+        anyhow::bail!(
+            "the clock format is {} characters wide, only {width} fit",
+            format.width()
+        );
+    }
+    Ok(format)
+}
+
+/// The clock row shared by the pages, its last column is the status indicator
+pub fn clock_row(ctx: &PageContext) -> String {
+    let clock = ctx
+        .settings
+        .clock_format
+        .format(ctx.now, ctx.settings.locale);
+    format!(
+        "{}{}",
+        crate::icon::pad(&clock, clock_width(ctx.settings.geometry)),
+        status_indicator(ctx)
+    )
+}
+
+/// Symbol of temperatures in `units`, such as °C
+pub fn unit_symbol(units: Units) -> String {
+    match units {
+        Units::Standard => units.suffix().to_string(),
+        _ => format!("{}{}", Icon::Degree.to_char(), units.suffix()),
+    }
+}
+
+/// Temperature rounded to whole degrees of `units`, right-aligned in three columns
+pub fn display_temperature(temperature: Option<Temperature>, units: Units) -> String {
+    match temperature {
+        Some(temperature) => format!("{:3}", temperature.in_units(units).round() as i32),
+        None => "  -".to_string(),
+    }
+}
+
+/// Icon of the sky: fog, clouds, or sun or moon for a clear one
+fn sky_icon(conditions: &Conditions) -> Option<Icon> {
+    if conditions.obscuration.is_some() {
+        return Some(Icon::Fog);
+    }
+    match conditions.clouds? {
+        Cloudiness::Clear | Cloudiness::Few if conditions.night => Some(Icon::Moon),
+        Cloudiness::Clear | Cloudiness::Few => Some(Icon::Sun),
+        Cloudiness::Scattered | Cloudiness::Broken | Cloudiness::Overcast => Some(Icon::Cloud),
+    }
+}
+
+fn precipitation_icon(conditions: &Conditions) -> Option<Icon> {
+    match (conditions.any_rain(), conditions.snow) {
+        (Some(_), Some(_)) => Some(Icon::Sleet),
+        (Some(_), None) => Some(Icon::Rain),
+        (None, Some(_)) => Some(Icon::Snow),
+        (None, None) => None,
+    }
+}
+
+fn storm_icon(conditions: &Conditions) -> Option<Icon> {
+    if conditions.thunder.is_some() {
+        Some(Icon::Thunder)
+    } else {
+        conditions.hazard.map(|_| Icon::Wind)
+    }
+}
+
+/// Sky, precipitation and storm icons of `conditions` in three columns
+pub fn display_conditions(conditions: &Conditions) -> String {
+    [
+        sky_icon(conditions),
+        precipitation_icon(conditions),
+        storm_icon(conditions),
+    ]
+    .into_iter()
+    .map(|icon| icon.map_or(' ', Icon::to_char))
+    .collect()
+}
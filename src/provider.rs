@@ -0,0 +1,257 @@
+use crate::health::Health;
+use crate::locale::Locale;
+use crate::timezone::LocalTimeZone;
+use crate::weather::Forecast;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+/// How long a provider is skipped after its first failure
+const BASE_COOLDOWN: Duration = Duration::from_secs(60);
+const MAX_COOLDOWN: Duration = Duration::from_secs(3600);
+
+pub type FetchFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<Forecast>> + 'a>>;
+
+/// A backend which is able to produce a weather forecast for a fixed location
+pub trait WeatherProvider {
+    /// Fetch the latest forecast
+    fn fetch(&self) -> FetchFuture<'_>;
+
+    /// When it's worth fetching again after a failure, `None` if there's no reason to wait
+    fn ready_at(&self) -> Option<Instant> {
+        None
+    }
+}
+
+/// Settings shared by all providers
+#[derive(Clone, Debug)]
+pub struct ProviderSettings {
+    pub latitude: String,
+    pub longitude: String,
+    pub appid: Option<String>,
+    pub user_agent: Option<String>,
+    /// Language of the texts of the providers which translate them
+    pub locale: Locale,
+    /// Time zone of the days of the providers which don't report daily summaries
+    pub timezone: LocalTimeZone,
+}
+
+/// Build the provider called `name` from `settings`
+pub fn from_name(
+    name: &str,
+    settings: &ProviderSettings,
+) -> anyhow::Result<Box<dyn WeatherProvider>> {
+    match name {
+        "owm" => Ok(Box::new(crate::owm::OwmProvider::new(settings)?)),
+        "owm-2.5" => Ok(Box::new(crate::owm25::Owm25Provider::new(settings)?)),
+        "nws" => Ok(Box::new(crate::nws::NwsProvider::new(settings)?)),
+        "met-no" => Ok(Box::new(crate::met_no::MetNoProvider::new(settings)?)),
+        "open-meteo" => Ok(Box::new(crate::open_meteo::OpenMeteoProvider::new(
+            settings,
+        ))),
+        _ => anyhow::bail!("unknown weather provider: {name}"),
+    }
+}
+
+/// Build a failover chain from a comma-separated list of provider names
+pub fn from_names(names: &str, settings: &ProviderSettings) -> anyhow::Result<Failover> {
+    let mut providers = Vec::new();
+    for name in names.split(',').map(str::trim) {
+        providers.push(TrackedProvider {
+            name: name.to_string(),
+            provider: from_name(name, settings)?,
+            health: RefCell::new(Health::new(BASE_COOLDOWN, MAX_COOLDOWN)),
+        });
+    }
+    Ok(Failover { providers })
+}
+
+struct TrackedProvider {
+    name: String,
+    provider: Box<dyn WeatherProvider>,
+    health: RefCell<Health>,
+}
+
+/// Ordered list of providers. Each fetch tries them in order until one succeeds, skipping the
+/// ones which are cooling down after a failure.
+pub struct Failover {
+    providers: Vec<TrackedProvider>,
+}
+
+impl Failover {
+    async fn fetch_forecast(&self) -> anyhow::Result<Forecast> {
+        let mut tried = false;
+        for tracked in &self.providers {
+            if tracked.health.borrow().in_backoff() {
+                continue;
+            }
+            tried = true;
+
+            match tracked.provider.fetch().await {
+                Ok(forecast) => {
+                    tracked.health.borrow_mut().succeeded();
+                    return Ok(forecast);
+                }
+                Err(e) => {
+                    let mut health = tracked.health.borrow_mut();
+                    health.failed();
+                    eprintln!("weather provider {} failed ({health}): {e:#}", tracked.name);
+                }
+            }
+        }
+
+        if tried {
+            anyhow::bail!("all weather providers failed")
+        } else {
+            anyhow::bail!("all weather providers are cooling down")
+        }
+    }
+}
+
+impl WeatherProvider for Failover {
+    fn fetch(&self) -> FetchFuture<'_> {
+        Box::pin(self.fetch_forecast())
+    }
+
+    /// The end of the earliest cooldown
+    fn ready_at(&self) -> Option<Instant> {
+        self.providers
+            .iter()
+            .filter_map(|p| p.health.borrow().backoff_until)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    /// Provider returning scripted results, counting the fetches
+    struct Scripted {
+        results: RefCell<VecDeque<anyhow::Result<Forecast>>>,
+        fetches: Rc<Cell<usize>>,
+    }
+
+    impl WeatherProvider for Scripted {
+        fn fetch(&self) -> FetchFuture<'_> {
+            self.fetches.set(self.fetches.get() + 1);
+            let result = self
+                .results
+                .borrow_mut()
+                .pop_front()
+                .expect("no result scripted");
+            Box::pin(async move { result })
+        }
+    }
+
+    /// Forecast telling which provider it came from
+    fn forecast(dt: i64) -> Forecast {
+        let mut forecast = Forecast::default();
+        forecast.current.dt = dt;
+        forecast
+    }
+
+    fn failure(name: &str) -> anyhow::Result<Forecast> {
+        Err(anyhow::anyhow!("{name} is down"))
+    }
+
+    /// Failover over providers returning `scripts` in order, with their fetch counters
+    fn failover(scripts: Vec<Vec<anyhow::Result<Forecast>>>) -> (Failover, Vec<Rc<Cell<usize>>>) {
+        let mut counters = Vec::new();
+        let providers = scripts
+            .into_iter()
+            .enumerate()
+            .map(|(i, results)| {
+                let fetches = Rc::new(Cell::new(0));
+                counters.push(Rc::clone(&fetches));
+                TrackedProvider {
+                    name: format!("provider {i}"),
+                    provider: Box::new(Scripted {
+                        results: RefCell::new(results.into()),
+                        fetches,
+                    }),
+                    health: RefCell::new(Health::new(BASE_COOLDOWN, MAX_COOLDOWN)),
+                }
+            })
+            .collect();
+        (Failover { providers }, counters)
+    }
+
+    fn fetch(failover: &Failover) -> anyhow::Result<Forecast> {
+        actix::System::new().block_on(failover.fetch())
+    }
+
+    fn fetches(counters: &[Rc<Cell<usize>>]) -> Vec<usize> {
+        counters.iter().map(|counter| counter.get()).collect()
+    }
+
+    #[test]
+    fn first_success_in_order() {
+        let (failover, counters) = failover(vec![
+            vec![failure("first")],
+            vec![Ok(forecast(1))],
+            vec![Ok(forecast(2))],
+        ]);
+        assert_eq!(fetch(&failover).unwrap().current.dt, 1);
+        assert_eq!(fetches(&counters), [1, 1, 0]);
+    }
+
+    #[test]
+    fn skips_providers_cooling_down() {
+        let (failover, counters) = failover(vec![
+            vec![failure("first"), Ok(forecast(0))],
+            vec![Ok(forecast(1)), Ok(forecast(2))],
+        ]);
+        assert_eq!(fetch(&failover).unwrap().current.dt, 1);
+        assert_eq!(fetch(&failover).unwrap().current.dt, 2);
+        assert_eq!(fetches(&counters), [1, 2]);
+    }
+
+    #[test]
+    fn all_failed_then_cooling_down() {
+        let (failover, counters) = failover(vec![vec![failure("first")], vec![failure("second")]]);
+        assert_eq!(
+            fetch(&failover).unwrap_err().to_string(),
+            "all weather providers failed"
+        );
+        assert_eq!(
+            fetch(&failover).unwrap_err().to_string(),
+            "all weather providers are cooling down"
+        );
+        assert_eq!(fetches(&counters), [1, 1]);
+    }
+
+    #[test]
+    fn ready_at_the_end_of_the_earliest_cooldown() {
+        let (failover, _) = failover(vec![vec![], vec![], vec![]]);
+        assert_eq!(failover.ready_at(), None);
+
+        let now = Instant::now();
+        let ends = [
+            Some(now + 2 * BASE_COOLDOWN),
+            Some(now + BASE_COOLDOWN),
+            None,
+        ];
+        for (tracked, end) in failover.providers.iter().zip(ends) {
+            tracked.health.borrow_mut().backoff_until = end;
+        }
+        assert_eq!(failover.ready_at(), Some(now + BASE_COOLDOWN));
+    }
+
+    #[test]
+    fn success_ends_the_cooldown() {
+        let (failover, _) = failover(vec![vec![failure("first"), Ok(forecast(1))]]);
+        assert!(fetch(&failover).is_err());
+        let ready_at = failover.ready_at().unwrap();
+        assert!(ready_at > Instant::now() + BASE_COOLDOWN / 2);
+
+        // the cooldown is over
+        failover.providers[0].health.borrow_mut().backoff_until = Some(Instant::now());
+        assert_eq!(fetch(&failover).unwrap().current.dt, 1);
+        assert_eq!(failover.ready_at(), None);
+    }
+}
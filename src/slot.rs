@@ -0,0 +1,342 @@
+use crate::condition::Conditions;
+use crate::geometry::Geometry;
+use crate::icon;
+use crate::locale::Locale;
+use crate::page::Arrangement;
+use crate::temperature::{Temperature, TemperatureKind};
+use crate::timezone::LocalTimeZone;
+use crate::weather::{DailyTemperatures, Forecast};
+use std::str::FromStr;
+use time::{Duration, OffsetDateTime, Time};
+
+/// Width of a slot column on the display
+pub const SLOT_WIDTH: usize = 3;
+
+/// Number of slot columns which fit in a row of a display of `geometry`, separated by spaces
+pub fn max_slots(geometry: Geometry) -> usize {
+    (Arrangement::of(geometry).row_width(geometry) + 1) / (SLOT_WIDTH + 1)
+}
+
+/// Slots shown unless configured, as many as fit on a display of `geometry`
+pub fn default_slots(geometry: Geometry) -> &'static str {
+    match max_slots(geometry) {
+        ..=4 => "now,+3h,+6h,tmr",
+        _ => "now,+2h,+4h,+6h,tmr",
+    }
+}
+
+/// Which of the daily temperatures to show
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DailyValue {
+    Min,
+    Max,
+    Day,
+    Night,
+}
+
+impl DailyValue {
+    pub fn of(&self, temperatures: &DailyTemperatures) -> Temperature {
+        match self {
+            DailyValue::Min => temperatures.min,
+            DailyValue::Max => temperatures.max,
+            DailyValue::Day => temperatures.day,
+            DailyValue::Night => temperatures.night,
+        }
+    }
+}
+
+/// A point or period of time to show the weather for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Slot {
+    /// Current conditions
+    Now,
+    /// The hour starting this many hours after the current one
+    Hours(i64),
+    /// The next hour containing this clock time
+    At(Time),
+    /// The day this many days after today
+    Daily { days: i64, value: DailyValue },
+}
+
+impl Slot {
+    /// Temperature and conditions of the slot relative to `now`, if the forecast covers it. The
+    /// feels-like temperature is selected instead of the actual one if `feels_like` is set.
+    pub fn select(
+        &self,
+        forecast: &Forecast,
+        now: OffsetDateTime,
+        tz: &LocalTimeZone,
+        feels_like: bool,
+    ) -> Option<(Temperature, Conditions)> {
+        let timestamp = now.unix_timestamp();
+        let hour_start = timestamp - timestamp.rem_euclid(3600);
+        let sample = match self {
+            Slot::Now => forecast.current_at(timestamp),
+            Slot::Hours(hours) => forecast.hour(hour_start + hours * 3600),
+            Slot::At(time) => {
+                let today = tz.to_local(now).date();
+                let mut at = tz.resolve(today.with_time(*time));
+                if at + Duration::HOUR <= now {
+                    at = tz.resolve(today.next_day()?.with_time(*time));
+                }
+                forecast.hour(at.unix_timestamp())
+            }
+            Slot::Daily { days, value } => {
+                let date = tz.to_local(now).date() + Duration::days(*days);
+                let day = forecast.date(date, tz)?;
+                let temperatures = match feels_like {
+                    true => &day.feels_like,
+                    false => &day.temperature,
+                };
+                return Some((value.of(temperatures), day.conditions));
+            }
+        };
+        sample.map(|s| match feels_like {
+            true => (s.feels_like, s.conditions),
+            false => (s.temperature, s.conditions),
+        })
+    }
+
+    fn default_label(&self, locale: Locale) -> String {
+        let labels = locale.labels();
+        match self {
+            Slot::Now => labels.now.to_string(),
+            Slot::Hours(hours) => format!("+{hours}h"),
+            Slot::At(time) if time.minute() == 0 => format!("{}h", time.hour()),
+            Slot::At(time) => format!("{}:{:02}", time.hour(), time.minute()),
+            Slot::Daily { days: 0, value } => match value {
+                DailyValue::Min => labels.min,
+                DailyValue::Max => labels.max,
+                DailyValue::Day => labels.day,
+                DailyValue::Night => labels.night,
+            }
+            .to_string(),
+            Slot::Daily { value, .. } => match value {
+                DailyValue::Min => labels.tomorrow_min,
+                DailyValue::Max => labels.tomorrow_max,
+                DailyValue::Day => labels.tomorrow,
+                DailyValue::Night => labels.tomorrow_night,
+            }
+            .to_string(),
+        }
+    }
+}
+
+impl FromStr for Slot {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        if s == "now" {
+            return Ok(Slot::Now);
+        }
+
+        if let Some(hours) = s.strip_prefix('+').and_then(|s| s.strip_suffix('h')) {
+            let hours = hours
+                .parse::<i64>()
+                .map_err(|e| anyhow::anyhow!("{e} in slot {s}, expected +Nh"))?;
+            if hours < 1 {
+                anyhow::bail!("slot {s} isn't ahead, expected +Nh with N of at least 1 or now");
+            }
+            return Ok(Slot::Hours(hours));
+        }
+
+        if let Some((hour, minute)) = s.split_once(':') {
+            let time = match (hour.parse(), minute.parse()) {
+                (Ok(hour), Ok(minute)) => Time::from_hms(hour, minute, 0).ok(),
+                _ => None,
+            };
+            return time
+                .map(Slot::At)
+                .ok_or_else(|| anyhow::anyhow!("invalid time in slot {s}, expected HH:MM"));
+        }
+
+        let (day, value) = s.split_once('-').unwrap_or((s, "day"));
+        let days = match day {
+            "today" => 0,
+            "tmr" => 1,
+            _ => anyhow::bail!("unknown slot: {s}"),
+        };
+        let value = match value {
+            "min" => DailyValue::Min,
+            "max" => DailyValue::Max,
+            "day" => DailyValue::Day,
+            "night" => DailyValue::Night,
+            _ => anyhow::bail!("unknown daily value in slot {s}, expected min, max, day or night"),
+        };
+        Ok(Slot::Daily { days, value })
+    }
+}
+
+/// A slot together with the temperature and the label shown for it
+#[derive(Clone, Debug)]
+pub struct SlotSpec {
+    pub slot: Slot,
+    pub kind: TemperatureKind,
+    pub label: String,
+}
+
+impl SlotSpec {
+    /// Parse `slot[/temperature][=label]`, showing `default_kind` temperature unless the slot sets
+    /// its own and labeling it in `locale` unless it has a label
+    fn parse(s: &str, default_kind: TemperatureKind, locale: Locale) -> anyhow::Result<Self> {
+        let (slot, label) = match s.split_once('=') {
+            Some((slot, label)) => (slot, Some(label.trim().to_string())),
+            None => (s, None),
+        };
+        let (slot, kind) = match slot.split_once('/') {
+            Some((slot, kind)) => (
+                slot,
+                kind.trim().parse().map_err(|e| {
+                    anyhow::anyhow!("{e} in slot {s:?}, expected actual, feels or both")
+                })?,
+            ),
+            None => (slot, default_kind),
+        };
+        let slot = slot.trim().parse::<Slot>()?;
+        let label = label.unwrap_or_else(|| slot.default_label(locale));
+
+        if icon::width(&label) > SLOT_WIDTH {
+            anyhow::bail!(
+                "label {label:?} of slot {s:?} is wider than {SLOT_WIDTH} cells, set a shorter one with {s}=label"
+            );
+        }
+
+        Ok(Self { slot, kind, label })
+    }
+}
+
+/// Parse a comma-separated list of slots, such as `now,+1h,+3h/both,18:00,tmr-max/actual=max`,
+/// for a display of `geometry`
+pub fn parse_slots(
+    s: &str,
+    default_kind: TemperatureKind,
+    locale: Locale,
+    geometry: Geometry,
+) -> anyhow::Result<Vec<SlotSpec>> {
+    let slots = s
+        .split(',')
+        .map(|slot| SlotSpec::parse(slot, default_kind, locale))
+        .collect::<anyhow::Result<Vec<SlotSpec>>>()?;
+
+    let max = max_slots(geometry);
+    if slots.is_empty() || slots.len() > max {
+        anyhow::bail!(
+            "between 1 and {max} slots fit on a {}x{} display",
+            geometry.cols,
+            geometry.rows
+        );
+    }
+
+    Ok(slots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> anyhow::Result<SlotSpec> {
+        SlotSpec::parse(s, TemperatureKind::FeelsLike, Locale::English)
+    }
+
+    fn error(s: &str) -> String {
+        parse(s).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn slots() {
+        assert_eq!(parse("now").unwrap().slot, Slot::Now);
+        assert_eq!(parse("+3h").unwrap().slot, Slot::Hours(3));
+        assert_eq!(
+            parse("18:30=eve").unwrap().slot,
+            Slot::At(Time::from_hms(18, 30, 0).unwrap())
+        );
+        assert_eq!(
+            parse("today").unwrap().slot,
+            Slot::Daily {
+                days: 0,
+                value: DailyValue::Day
+            }
+        );
+        assert_eq!(
+            parse("tmr-night").unwrap().slot,
+            Slot::Daily {
+                days: 1,
+                value: DailyValue::Night
+            }
+        );
+    }
+
+    #[test]
+    fn kind_and_label() {
+        let spec = parse("+1h").unwrap();
+        assert_eq!(spec.kind, TemperatureKind::FeelsLike);
+        assert_eq!(spec.label, "+1h");
+
+        let spec = parse("tmr-max/both=hi").unwrap();
+        assert_eq!(spec.kind, TemperatureKind::Both);
+        assert_eq!(spec.label, "hi");
+    }
+
+    #[test]
+    fn default_labels() {
+        let label = |s| parse(s).unwrap().label;
+        assert_eq!(label("now"), "now");
+        assert_eq!(label("18:00"), "18h");
+        assert_eq!(label("tmr"), "tmr");
+        assert_eq!(label("today"), "day");
+    }
+
+    #[test]
+    fn today_and_tomorrow_labels_differ() {
+        for value in ["min", "max", "day", "night"] {
+            let today = parse(&format!("today-{value}")).unwrap().label;
+            let tomorrow = parse(&format!("tmr-{value}")).unwrap().label;
+            assert_ne!(today, tomorrow, "{value}");
+        }
+    }
+
+    #[test]
+    fn hours_have_to_be_ahead() {
+        assert_eq!(
+            error("+0h"),
+            "slot +0h isn't ahead, expected +Nh with N of at least 1 or now"
+        );
+        assert_eq!(
+            error("+-1h"),
+            "slot +-1h isn't ahead, expected +Nh with N of at least 1 or now"
+        );
+        assert_eq!(
+            error("+xh"),
+            "invalid digit found in string in slot +xh, expected +Nh"
+        );
+    }
+
+    #[test]
+    fn invalid_slots() {
+        assert_eq!(error("25:00"), "invalid time in slot 25:00, expected HH:MM");
+        assert_eq!(error("yesterday"), "unknown slot: yesterday");
+        assert_eq!(
+            error("tmr-noon"),
+            "unknown daily value in slot tmr-noon, expected min, max, day or night"
+        );
+        assert_eq!(
+            error("now/warm"),
+            "unknown temperature \"warm\" in slot \"now/warm\", expected actual, feels or both"
+        );
+        assert_eq!(
+            error("now=current"),
+            "label \"current\" of slot \"now=current\" is wider than 3 cells, set a shorter one with now=current=label"
+        );
+    }
+
+    #[test]
+    fn slots_have_to_fit() {
+        let geometry = "16x2".parse().unwrap();
+        let parse = |s| parse_slots(s, TemperatureKind::Actual, Locale::English, geometry);
+        assert_eq!(parse(default_slots(geometry)).unwrap().len(), 4);
+        assert_eq!(
+            parse("now,+1h,+2h,+3h,+4h").unwrap_err().to_string(),
+            "between 1 and 4 slots fit on a 16x2 display"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Golden-file tests of every page on every display size, rendered through the emulated display.
+//! Run with `UPDATE_GOLDEN=1` to write the files after an intended change of what's shown.
+
+use crate::condition::{Cloudiness, Conditions, Intensity};
+use crate::emulator::{Emulator, Hd44780};
+use crate::emulator_renderer::framed;
+use crate::geometry::Geometry;
+use crate::lcd::Lcd;
+use crate::page::Arrangement;
+use crate::slot::default_slots;
+use crate::source::tests::{settings, source};
+use crate::source::FRAME;
+use crate::temperature::Temperature;
+use crate::weather::{Alert, DailySample, DailyTemperatures, Details, Forecast, Sample};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use time::macros::datetime;
+use time::OffsetDateTime;
+
+const GEOMETRIES: [&str; 5] = ["16x2", "16x4", "20x2", "20x4", "40x2"];
+
+/// Wednesday 18 March 2026, 14:05:30 in Prague
+const NOW: OffsetDateTime = datetime!(2026-03-18 13:05:30 UTC);
+
+/// Layout of the custom page, narrow enough for every display
+const LAYOUT: &str = "{now:%H:%M} {slot.0.temp:>3}{unit} {status}
+{if alert}{alert:<16}{else}{sunrise}-{sunset}{end}
+{slot.1.label:>3}{slot.1.icons} {slot.1.temp:>3}{unit}
+min {today.min:>3} max {today.max:>3}";
+
+fn details() -> Details {
+    Details {
+        wind_speed: Some(4.3),
+        wind_gust: Some(8.9),
+        wind_direction: Some(338.0),
+        humidity: Some(71.0),
+        pressure: Some(1012.4),
+        dew_point: Some(Temperature::from_celsius(7.4)),
+        uv_index: Some(2.3),
+        clouds: Some(48.0),
+        visibility: Some(24140.0),
+    }
+}
+
+/// Scattered clouds turning into light rain showers and a thunderstorm in the evening
+fn conditions(hour: i64) -> Conditions {
+    let clouds = Conditions::clouded(Cloudiness::Scattered);
+    match hour {
+        0..=2 => clouds,
+        3..=5 => Conditions {
+            rain: Some(Intensity::Light),
+            showers: true,
+            ..clouds
+        },
+        6..=7 => Conditions {
+            thunder: Some(Intensity::Moderate),
+            rain: Some(Intensity::Moderate),
+            ..Conditions::clouded(Cloudiness::Overcast)
+        },
+        _ => Conditions {
+            night: true,
+            ..Conditions::clouded(Cloudiness::Few)
+        },
+    }
+}
+
+/// Forecast fetched a quarter of an hour before `NOW`
+fn forecast() -> Forecast {
+    let hour_start = datetime!(2026-03-18 13:00 UTC).unix_timestamp();
+    let hourly = (0..36)
+        .map(|hour| {
+            let temperature = 12.4 - (hour as f64 - 3.0).abs() * 0.6;
+            Sample {
+                dt: hour_start + hour * 3600,
+                temperature: Temperature::from_celsius(temperature),
+                feels_like: Temperature::from_celsius(temperature - 1.6),
+                conditions: conditions(hour),
+                details: Details::default(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let current = Sample {
+        dt: hour_start,
+        details: details(),
+        ..hourly[0].clone()
+    };
+
+    // days starting at the midnights in Prague
+    let day_start = datetime!(2026-03-17 23:00 UTC).unix_timestamp();
+    let daily = [
+        (3.1, 13.0, 1.2, 11.4),
+        (1.8, 9.6, -1.0, 7.9),
+        (-0.4, 7.2, -3.3, 5.0),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(day, (min, max, feels_min, feels_max))| {
+        let dt = day_start + day as i64 * 86400;
+        DailySample {
+            dt,
+            temperature: DailyTemperatures::from_range(
+                Temperature::from_celsius(min),
+                Temperature::from_celsius(max),
+            ),
+            feels_like: DailyTemperatures::from_range(
+                Temperature::from_celsius(feels_min),
+                Temperature::from_celsius(feels_max),
+            ),
+            conditions: conditions(3 + day as i64),
+            sunrise: Some(dt + 6 * 3600 + 4 * 60),
+            sunset: Some(dt + 18 * 3600 + 3 * 60),
+            details: Details::default(),
+        }
+    })
+    .collect();
+
+    let alerts = vec![
+        Alert {
+            event: "Strong wind gusts in the afternoon".to_string(),
+            start: datetime!(2026-03-18 10:00 UTC).unix_timestamp(),
+            end: datetime!(2026-03-18 20:00 UTC).unix_timestamp(),
+        },
+        Alert {
+            event: "Thunderstorms".to_string(),
+            start: datetime!(2026-03-18 12:00 UTC).unix_timestamp(),
+            end: datetime!(2026-03-18 22:00 UTC).unix_timestamp(),
+        },
+    ];
+
+    Forecast {
+        current,
+        hourly,
+        daily,
+        alerts,
+    }
+}
+
+/// What the emulated display of `geometry` shows on `page` from `NOW` on, one frame after
+/// another until the cycling rows and the alternating values have all been shown
+fn glass(page: &str, geometry: Geometry) -> String {
+    let settings = settings(
+        geometry,
+        page,
+        default_slots(geometry),
+        Some(&layout(geometry)),
+    );
+    let fetched_at = NOW.unix_timestamp() - 15 * 60;
+    let (mut source, now) = source(settings, forecast(), Some(fetched_at));
+
+    let controller = Rc::new(RefCell::new(Hd44780::default()));
+    let mut lcd = Lcd::emulated(Emulator::new(Rc::clone(&controller)), geometry).unwrap();
+    // three rows cycle below the first one on two-row displays, values alternate every frame
+    let frames = match Arrangement::of(geometry) {
+        Arrangement::Cycling => 6,
+        _ => 2,
+    };
+    (0..frames)
+        .map(|frame| {
+            now.set(NOW + frame * FRAME);
+            lcd.show(&source.render()).unwrap();
+            let glass = controller.borrow().glass(geometry);
+            framed(&glass, geometry)
+        })
+        .collect()
+}
+
+/// The rows of the custom layout which fit on a display of `geometry`
+fn layout(geometry: Geometry) -> String {
+    LAYOUT
+        .lines()
+        .take(geometry.rows)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn assert_golden(page: &str) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut mismatches = Vec::new();
+    for geometry in GEOMETRIES {
+        let actual = glass(page, geometry.parse().unwrap());
+        let path = dir.join(format!("{page}-{geometry}.txt"));
+        if update {
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+        if actual != expected {
+            mismatches.push(format!(
+                "{}:\nexpected\n{expected}actual\n{actual}",
+                path.display()
+            ));
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
+
+#[test]
+fn forecast_page() {
+    assert_golden("forecast");
+}
+
+#[test]
+fn today_page() {
+    assert_golden("today");
+}
+
+#[test]
+fn sun_page() {
+    assert_golden("sun");
+}
+
+#[test]
+fn alerts_page() {
+    assert_golden("alerts");
+}
+
+#[test]
+fn details_page() {
+    assert_golden("details");
+}
+
+#[test]
+fn custom_page() {
+    assert_golden("custom");
+}
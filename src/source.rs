@@ -1,22 +1,125 @@
-use crate::weather::{WeatherData, WeatherEntry};
+use crate::geometry::Geometry;
+use crate::locale::Locale;
+use crate::page::{PageContext, PageSpec, Status};
+use crate::slot::SlotSpec;
+use crate::temperature::{TemperatureKind, Units};
+use crate::time_format::TimeFormat;
+use crate::timezone::LocalTimeZone;
+use crate::weather::{Forecast, WeatherUpdate};
 use crate::Text;
 use actix::prelude::*;
-use std::time::Duration;
-use time::format_description::FormatItem;
-use time::macros::offset;
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
+
+/// How long each of the values which don't fit at once is shown
+pub const FRAME: Duration = Duration::from_secs(5);
+
+/// What to show on the display and how
+#[derive(Debug)]
+pub struct Settings {
+    /// Age after which the weather data is considered stale
+    pub stale_after: Duration,
+    /// Forecast slots shown in the columns
+    pub slots: Vec<SlotSpec>,
+    pub timezone: LocalTimeZone,
+    pub units: Units,
+    /// Temperature shown outside of the slots and in the slots which don't set their own
+    pub temperature: TemperatureKind,
+    /// Pages shown in rotation
+    pub pages: Vec<PageSpec>,
+    pub geometry: Geometry,
+    pub locale: Locale,
+    pub clock_format: TimeFormat,
+    /// How often the shown time changes
+    pub refresh: Duration,
+    /// Show the wind force on the Beaufort scale instead of the wind speed
+    pub beaufort: bool,
+}
+
+/// Where the current time comes from
+type Clock = Box<dyn Fn() -> OffsetDateTime>;
 
 pub struct Source {
     subscriber: Recipient<Text>,
-    weather_data: WeatherData,
+    clock: Clock,
+    /// Full forecast series, the displayed entries are selected from it on every tick
+    forecast: Forecast,
+    /// Unix timestamp of the last successful fetch, if any
+    fetched_at: Option<i64>,
+    /// Error of the last fetch, cleared by the next successful one
+    error: Option<String>,
+    settings: Settings,
+    /// Index of the shown page
+    page: usize,
+    /// When the shown page is replaced by the next one
+    page_until: Instant,
 }
 
 impl Source {
-    pub fn with_subscriber(subscriber: Recipient<Text>) -> Self {
+    pub fn with_subscriber(subscriber: Recipient<Text>, settings: Settings) -> Self {
+        let page_until = Instant::now() + settings.pages[0].dwell;
         Self {
             subscriber,
-            weather_data: WeatherData::zero(),
+            clock: Box::new(OffsetDateTime::now_utc),
+            forecast: Forecast::default(),
+            fetched_at: None,
+            error: None,
+            settings,
+            page: 0,
+            page_until,
+        }
+    }
+
+    /// Take the time from `clock` instead of the system clock
+    #[cfg(test)]
+    pub fn with_clock(self, clock: impl Fn() -> OffsetDateTime + 'static) -> Self {
+        Self {
+            clock: Box::new(clock),
+            ..self
+        }
+    }
+
+    fn status(&self, now: OffsetDateTime) -> Status {
+        let Some(fetched_at) = self.fetched_at else {
+            return Status::NoData;
+        };
+        let fetched_at = OffsetDateTime::from_unix_timestamp(fetched_at).unwrap_or(now);
+        if now - fetched_at > self.settings.stale_after {
+            Status::Stale { since: fetched_at }
+        } else {
+            Status::Fresh
         }
     }
+
+    fn next_page(&mut self) {
+        self.page = (self.page + 1) % self.settings.pages.len();
+        self.page_until = Instant::now() + self.settings.pages[self.page].dwell;
+    }
+
+    /// Render the shown page, moving on to the next one when its time is up or it has nothing
+    /// to show
+    pub fn render(&mut self) -> Text {
+        if Instant::now() >= self.page_until {
+            self.next_page();
+        }
+
+        let now = self.settings.timezone.to_local((self.clock)());
+        for _ in 0..self.settings.pages.len() {
+            let ctx = PageContext {
+                now,
+                forecast: &self.forecast,
+                status: self.status(now),
+                error: self.error.as_deref(),
+                settings: &self.settings,
+                frame: now.unix_timestamp() as u64 / FRAME.as_secs(),
+            };
+            if let Some(text) = self.settings.pages[self.page].page.render(&ctx) {
+                return text;
+            }
+            self.next_page();
+        }
+        Text::new(self.settings.geometry)
+    }
 }
 
 impl Actor for Source {
@@ -27,76 +130,15 @@ impl Actor for Source {
     }
 }
 
-fn space_or(value: bool, c: char) -> char {
-    match value {
-        true => c,
-        false => ' ',
-    }
-}
-
-fn display_rain_situation(we: &WeatherEntry) -> String {
-    let rs = we.rain_situation;
-    format!(
-        "{}{}{} ",
-        space_or(rs.rain, 'R'),
-        space_or(rs.snow, 'S'),
-        space_or(rs.thunder, 'T'),
-    )
-}
-
 #[derive(Default, Message)]
 #[rtype(result = "()")]
 struct Tick;
 
-lazy_static::lazy_static! {
-    static ref FORMAT: &'static [FormatItem<'static>] = time::macros::format_description!(
-        "[weekday repr:short] [month repr:short] [day] [hour]:[minute]:[second]"
-    );
-}
-
 impl Handler<Tick> for Source {
     type Result = ();
 
     fn handle(&mut self, _: Tick, ctx: &mut Self::Context) {
-        let time = time::OffsetDateTime::now_utc().to_offset(offset!(+2));
-        let datetime = time.format(&FORMAT).unwrap().parse().unwrap();
-
-        let temperature = format!(
-            "{:3} {:3} {:3} {:3} {:3}",
-            self.weather_data.entries[0].temperature,
-            self.weather_data.entries[1].temperature,
-            self.weather_data.entries[2].temperature,
-            self.weather_data.entries[3].temperature,
-            self.weather_data.entries[4].temperature,
-        )
-        .parse()
-        .unwrap();
-
-        let conditions = format!(
-            "{}{}{}{}{}",
-            display_rain_situation(&self.weather_data.entries[0]),
-            display_rain_situation(&self.weather_data.entries[1]),
-            display_rain_situation(&self.weather_data.entries[2]),
-            display_rain_situation(&self.weather_data.entries[3]),
-            display_rain_situation(&self.weather_data.entries[4]),
-        )
-        .parse()
-        .unwrap();
-
-        let labels = format!(
-            "{:3} {:3} {:3} {:3} {:3}",
-            self.weather_data.entries[0].label,
-            self.weather_data.entries[1].label,
-            self.weather_data.entries[2].label,
-            self.weather_data.entries[3].label,
-            self.weather_data.entries[4].label,
-        )
-        .parse()
-        .unwrap();
-
-        let text = Text {
-            rows: [datetime, labels, temperature, conditions],
-        };
+        let text = self.render();
 
         let sub = self.subscriber.clone();
         async move {
@@ -105,14 +147,243 @@ impl Handler<Tick> for Source {
         .into_actor(self)
         .wait(ctx);
 
-        ctx.notify_later(Tick, Duration::from_secs(10));
+        // render again right after the shown time or the frame changes
+        let step = self.settings.refresh.min(FRAME).as_nanos() as i128;
+        let nanos = (self.clock)().unix_timestamp_nanos();
+        let until_next_step = Duration::from_nanos((step - nanos.rem_euclid(step)) as u64);
+        let until_next_page = self.page_until.saturating_duration_since(Instant::now());
+        ctx.notify_later(Tick, until_next_step.min(until_next_page));
     }
 }
 
-impl Handler<WeatherData> for Source {
+impl Handler<WeatherUpdate> for Source {
     type Result = ();
 
-    fn handle(&mut self, wd: WeatherData, _: &mut Self::Context) {
-        self.weather_data = wd;
+    fn handle(&mut self, update: WeatherUpdate, _: &mut Self::Context) {
+        match update {
+            WeatherUpdate::Fetched {
+                forecast,
+                fetched_at,
+            } => {
+                self.forecast = forecast;
+                self.fetched_at = Some(fetched_at);
+                self.error = None;
+            }
+            WeatherUpdate::Failed { error } => self.error = Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::temperature::Temperature;
+    use crate::template::Template;
+    use crate::weather::{DailySample, DailyTemperatures, Sample};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use time::macros::datetime;
+
+    /// Europe/Prague, written out so that the tests don't depend on the time zone database
+    pub const PRAGUE: &str = "CET-1CEST,M3.5.0,M10.5.0/3";
+
+    struct Discard;
+
+    impl Actor for Discard {
+        type Context = Context<Self>;
+    }
+
+    impl Handler<Text> for Discard {
+        type Result = ();
+
+        fn handle(&mut self, _: Text, _: &mut Self::Context) {}
+    }
+
+    /// Settings of a display of `geometry` in Prague showing `pages` with `slots`, the custom page
+    /// laid out by `layout`
+    pub fn settings(
+        geometry: Geometry,
+        pages: &str,
+        slots: &str,
+        layout: Option<&str>,
+    ) -> Settings {
+        let locale = Locale::English;
+        let temperature = TemperatureKind::Actual;
+        let slots = crate::slot::parse_slots(slots, temperature, locale, geometry).unwrap();
+        let layout = layout.map(|layout| Template::compile(layout, slots.len(), geometry).unwrap());
+        let clock_format = crate::page::clock_format(None, geometry).unwrap();
+        Settings {
+            stale_after: Duration::from_secs(3 * 3600),
+            slots,
+            timezone: LocalTimeZone::load(Some(PRAGUE)).unwrap(),
+            units: Units::Metric,
+            temperature,
+            pages: crate::page::parse_pages(pages, layout.as_ref()).unwrap(),
+            geometry,
+            locale,
+            refresh: clock_format.resolution(),
+            clock_format,
+            beaufort: false,
+        }
+    }
+
+    /// Source rendering `forecast` fetched at unix timestamp `fetched_at` at the time of the
+    /// returned clock
+    pub fn source(
+        settings: Settings,
+        forecast: Forecast,
+        fetched_at: Option<i64>,
+    ) -> (Source, Rc<Cell<OffsetDateTime>>) {
+        let subscriber = System::new().block_on(async { Discard.start().recipient() });
+        let now = Rc::new(Cell::new(OffsetDateTime::UNIX_EPOCH));
+        let clock = Rc::clone(&now);
+        let mut source =
+            Source::with_subscriber(subscriber, settings).with_clock(move || clock.get());
+        source.forecast = forecast;
+        source.fetched_at = fetched_at;
+        (source, now)
+    }
+
+    fn rows(text: &Text) -> Vec<&str> {
+        text.rows.iter().map(crate::LimitedString::as_str).collect()
+    }
+
+    /// A day starting at `start` with the maximum temperature `max`
+    fn day(start: OffsetDateTime, max: f64) -> DailySample {
+        let max = Temperature::from_celsius(max);
+        let min = Temperature::from_celsius(0.0);
+        DailySample {
+            dt: start.unix_timestamp(),
+            temperature: DailyTemperatures::from_range(min, max),
+            feels_like: DailyTemperatures::from_range(min, max),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn clock_follows_spring_forward() {
+        let (mut source, now) = source(
+            settings(Geometry::LCD_20X4, "forecast", "now", None),
+            Forecast::default(),
+            None,
+        );
+
+        now.set(datetime!(2026-03-29 00:59:59 UTC));
+        assert_eq!(rows(&source.render())[0], "Sun Mar 29 01:59:59?");
+        now.set(datetime!(2026-03-29 01:00:00 UTC));
+        assert_eq!(rows(&source.render())[0], "Sun Mar 29 03:00:00?");
+    }
+
+    #[test]
+    fn clock_follows_fall_back() {
+        let (mut source, now) = source(
+            settings(Geometry::LCD_20X4, "forecast", "now", None),
+            Forecast::default(),
+            None,
+        );
+
+        now.set(datetime!(2026-10-25 00:59:59 UTC));
+        assert_eq!(rows(&source.render())[0], "Sun Oct 25 02:59:59?");
+        now.set(datetime!(2026-10-25 01:00:00 UTC));
+        assert_eq!(rows(&source.render())[0], "Sun Oct 25 02:00:00?");
+    }
+
+    #[test]
+    fn tomorrow_on_the_evening_before_spring_forward() {
+        // Saturday, Sunday with the clocks going forward and Monday
+        let forecast = Forecast {
+            daily: vec![
+                day(datetime!(2026-03-27 23:00 UTC), 10.0),
+                day(datetime!(2026-03-28 23:00 UTC), 20.0),
+                day(datetime!(2026-03-29 22:00 UTC), 30.0),
+            ],
+            ..Default::default()
+        };
+        let (mut source, now) = source(
+            settings(Geometry::LCD_20X4, "forecast", "now,tmr", None),
+            forecast,
+            None,
+        );
+
+        // Saturday 23:30 local time, less than a day before Monday
+        now.set(datetime!(2026-03-28 22:30 UTC));
+        let degree = crate::icon::Icon::Degree.to_char();
+        assert_eq!(rows(&source.render())[2], format!("  -  20{degree}C"));
+    }
+
+    #[test]
+    fn today_in_the_last_hour_of_fall_back() {
+        // Sunday with the clocks going back lasts 25 hours
+        let forecast = Forecast {
+            daily: vec![
+                day(datetime!(2026-10-24 22:00 UTC), 15.0),
+                day(datetime!(2026-10-25 23:00 UTC), 25.0),
+            ],
+            ..Default::default()
+        };
+        let (mut source, now) = source(
+            settings(Geometry::LCD_20X4, "today", "now", None),
+            forecast,
+            None,
+        );
+
+        // Sunday 23:30 local time, 25 and a half hours after its start
+        now.set(datetime!(2026-10-25 22:30 UTC));
+        let text = source.render();
+        assert_eq!(rows(&text)[0], "Sun Oct 25 23:30:00?");
+        assert_eq!(rows(&text)[2], "low   0  high  15");
+    }
+
+    #[test]
+    fn today_shows_the_configured_temperature() {
+        let current = datetime!(2026-03-18 13:00 UTC);
+        let forecast = Forecast {
+            current: Sample {
+                dt: current.unix_timestamp(),
+                temperature: Temperature::from_celsius(10.0),
+                feels_like: Temperature::from_celsius(5.0),
+                ..Default::default()
+            },
+            daily: vec![day(datetime!(2026-03-17 23:00 UTC), 12.0)],
+            ..Default::default()
+        };
+        let mut settings = settings(Geometry::LCD_20X4, "today", "now", None);
+        settings.temperature = TemperatureKind::Both;
+        let (mut source, now) = source(settings, forecast, None);
+
+        // the feels-like temperature in the alternate frames
+        now.set(current);
+        assert_eq!(rows(&source.render())[1], "today      now  10");
+        now.set(current + FRAME);
+        assert_eq!(rows(&source.render())[1], "today      now   5");
+    }
+
+    #[test]
+    fn today_in_german_on_a_narrow_display() {
+        let current = datetime!(2026-03-18 13:00 UTC);
+        let forecast = Forecast {
+            current: Sample {
+                dt: current.unix_timestamp(),
+                temperature: Temperature::from_celsius(10.0),
+                ..Default::default()
+            },
+            daily: vec![day(datetime!(2026-03-17 23:00 UTC), 12.0)],
+            ..Default::default()
+        };
+        let mut settings = settings("16x4".parse().unwrap(), "today", "now", None);
+        settings.locale = Locale::German;
+        let (mut source, now) = source(settings, forecast, None);
+
+        now.set(current);
+        let text = source.render();
+        assert_eq!(
+            rows(&text),
+            [
+                "Mi  18 14:00:00?",
+                "    jetzt 10",
+                "min   0  max  12",
+                "gefühlt 12 / 0"
+            ]
+        );
     }
 }
@@ -1,4 +1,5 @@
-use crate::Text;
+use crate::icon::Icon;
+use crate::{LimitedString, Text};
 use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
@@ -8,7 +9,6 @@ use ratatui::{
     widgets::Paragraph,
 };
 use std::io::stdout;
-use std::time::Duration;
 
 use actix::prelude::*;
 
@@ -31,11 +31,10 @@ impl Default for StdoutRenderer {
 impl Actor for StdoutRenderer {
     type Context = Context<Self>;
 
-    fn started(&mut self, ctx: &mut Self::Context) {
+    fn started(&mut self, _: &mut Self::Context) {
         stdout().execute(EnterAlternateScreen).unwrap();
         enable_raw_mode().unwrap();
         self.terminal.clear().unwrap();
-        ctx.notify(Tick);
     }
 
     fn stopped(&mut self, _: &mut Self::Context) {
@@ -44,47 +43,28 @@ impl Actor for StdoutRenderer {
     }
 }
 
+/// Replace the icons of `row` with Unicode glyphs
+fn glyphs(row: &LimitedString) -> String {
+    row.as_str()
+        .chars()
+        .map(|c| Icon::from_char(c).map_or(c, Icon::glyph))
+        .collect()
+}
+
 impl Handler<Text> for StdoutRenderer {
     type Result = ();
 
     fn handle(&mut self, text: Text, _: &mut Self::Context) {
         self.text = text;
-    }
-}
-
-#[derive(Default, Message)]
-#[rtype(result = "()")]
-pub struct Tick;
-
-impl Handler<Tick> for StdoutRenderer {
-    type Result = ();
-
-    fn handle(&mut self, _: Tick, ctx: &mut Self::Context) {
         self.terminal
             .draw(|frame| {
-                frame.render_widget(
-                    Paragraph::new(&self.text.rows[0]).white().on_black(),
-                    Rect::new(0, 0, 20, 1),
-                );
-                frame.render_widget(
-                    Paragraph::new(&self.text.rows[1]).white().on_black(),
-                    Rect::new(0, 1, 20, 1),
-                );
-                frame.render_widget(
-                    Paragraph::new(&self.text.rows[2]).white().on_black(),
-                    Rect::new(0, 2, 20, 1),
-                );
-                frame.render_widget(
-                    Paragraph::new(&self.text.rows[3]).white().on_black(),
-                    Rect::new(0, 3, 20, 1),
-                );
+                for (i, row) in self.text.rows.iter().enumerate() {
+                    frame.render_widget(
+                        Paragraph::new(glyphs(row)).white().on_black(),
+                        Rect::new(0, i as u16, self.text.geometry.cols as u16, 1),
+                    );
+                }
             })
             .unwrap();
-
-        // for row in &self.text.rows {
-        //     println!("{}", row);
-        // }
-
-        ctx.notify_later(Tick, Duration::from_secs(5));
     }
 }
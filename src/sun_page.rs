@@ -0,0 +1,59 @@
+use crate::icon;
+use crate::locale::Locale;
+use crate::page::{arrange, clock_row, fit, Page, PageContext, TIME_FORMAT};
+use crate::Text;
+use time::OffsetDateTime;
+
+/// Length of the synodic month in days
+const SYNODIC_MONTH: f64 = 29.530588853;
+
+/// Unix timestamp of the new moon of 6 January 2000, 18:14 UTC
+const NEW_MOON: i64 = 947182440;
+
+/// Name of the moon phase at `time` in `locale`
+pub fn moon_phase(time: OffsetDateTime, locale: Locale) -> &'static str {
+    let age = ((time.unix_timestamp() - NEW_MOON) as f64 / 86400.0).rem_euclid(SYNODIC_MONTH);
+    // eight phases, each centered on its principal point
+    let phase = (age / SYNODIC_MONTH * 8.0).round() as usize % 8;
+    locale.phrases().moon_phases[phase]
+}
+
+/// Today's sunrise and sunset and the moon phase
+#[derive(Debug)]
+pub struct SunPage;
+
+impl Page for SunPage {
+    fn render(&self, ctx: &PageContext) -> Option<Text> {
+        let today = ctx.today();
+        let time = |timestamp: Option<i64>| {
+            timestamp
+                .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+                .map(|time| {
+                    let time = ctx.settings.timezone.to_local(time);
+                    time.format(&TIME_FORMAT).unwrap()
+                })
+                .unwrap_or_else(|| "--:--".to_string())
+        };
+
+        let phrases = ctx.settings.locale.phrases();
+        // the times are aligned
+        let width = icon::width(phrases.sunrise).max(icon::width(phrases.sunset));
+        let sunrise = format!(
+            "{} {}",
+            icon::pad(phrases.sunrise, width),
+            time(today.and_then(|day| day.sunrise))
+        );
+        let sunset = format!(
+            "{} {}",
+            icon::pad(phrases.sunset, width),
+            time(today.and_then(|day| day.sunset))
+        );
+        let phase = moon_phase(ctx.now, ctx.settings.locale);
+        let moon = fit(
+            ctx,
+            &[format!("{} {phase}", phrases.moon), phase.to_string()],
+        );
+
+        Some(arrange(ctx, &[clock_row(ctx), sunrise, sunset, moon]))
+    }
+}
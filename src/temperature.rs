@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Units to display temperatures in
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Units {
+    /// Kelvin
+    Standard,
+    /// Degrees Celsius
+    Metric,
+    /// Degrees Fahrenheit
+    Imperial,
+}
+
+impl Units {
+    /// Symbol displayed after temperatures
+    pub fn suffix(&self) -> char {
+        match self {
+            Units::Standard => 'K',
+            Units::Metric => 'C',
+            Units::Imperial => 'F',
+        }
+    }
+}
+
+impl FromStr for Units {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Units::Standard),
+            "metric" => Ok(Units::Metric),
+            "imperial" => Ok(Units::Imperial),
+            _ => Err(format!("unknown units {s:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for Units {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl configure_me::parse_arg::ParseArgFromStr for Units {
+    fn describe_type<W: std::fmt::Write>(mut writer: W) -> std::fmt::Result {
+        write!(writer, "standard, metric or imperial")
+    }
+}
+
+/// Which temperature to show in a slot
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum TemperatureKind {
+    /// Air temperature
+    Actual,
+    /// Apparent temperature
+    FeelsLike,
+    /// Both of them, alternating on every render
+    Both,
+}
+
+impl TemperatureKind {
+    /// Whether the feels-like temperature is shown in the `alternate` render
+    pub fn shows_feels_like(&self, alternate: bool) -> bool {
+        match self {
+            TemperatureKind::Actual => false,
+            TemperatureKind::FeelsLike => true,
+            TemperatureKind::Both => alternate,
+        }
+    }
+}
+
+impl FromStr for TemperatureKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "actual" => Ok(TemperatureKind::Actual),
+            "feels" => Ok(TemperatureKind::FeelsLike),
+            "both" => Ok(TemperatureKind::Both),
+            _ => Err(format!("unknown temperature {s:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for TemperatureKind {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl configure_me::parse_arg::ParseArgFromStr for TemperatureKind {
+    fn describe_type<W: std::fmt::Write>(mut writer: W) -> std::fmt::Result {
+        write!(writer, "actual, feels or both")
+    }
+}
+
+/// A temperature, stored in degrees Celsius regardless of the units it was reported or is
+/// displayed in
+#[derive(Default, Clone, Copy, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Temperature {
+    celsius: f32,
+}
+
+impl Temperature {
+    pub fn from_celsius(celsius: f64) -> Self {
+        Self {
+            celsius: celsius as f32,
+        }
+    }
+
+    pub fn from_fahrenheit(fahrenheit: f64) -> Self {
+        Self::from_celsius((fahrenheit - 32.0) * 5.0 / 9.0)
+    }
+
+    pub fn in_units(&self, units: Units) -> f32 {
+        match units {
+            Units::Standard => self.celsius + 273.15,
+            Units::Metric => self.celsius,
+            Units::Imperial => self.celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            celsius: self.celsius.min(other.celsius),
+        }
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            celsius: self.celsius.max(other.celsius),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::display_temperature;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn conversions() {
+        let freezing = Temperature::from_celsius(0.0);
+        assert_close(freezing.in_units(Units::Metric), 0.0);
+        assert_close(freezing.in_units(Units::Standard), 273.15);
+        assert_close(freezing.in_units(Units::Imperial), 32.0);
+
+        let temperature = Temperature::from_fahrenheit(-40.0);
+        assert_close(temperature.in_units(Units::Metric), -40.0);
+        assert_close(temperature.in_units(Units::Imperial), -40.0);
+        assert_close(temperature.in_units(Units::Standard), 233.15);
+
+        let temperature = Temperature::from_fahrenheit(98.6);
+        assert_close(temperature.in_units(Units::Metric), 37.0);
+    }
+
+    #[test]
+    fn display_rounding() {
+        let display =
+            |celsius, units| display_temperature(Some(Temperature::from_celsius(celsius)), units);
+        assert_eq!(display(21.5, Units::Metric), " 22");
+        assert_eq!(display(21.4, Units::Metric), " 21");
+        assert_eq!(display(-2.5, Units::Metric), " -3");
+        assert_eq!(display(-12.4, Units::Metric), "-12");
+        assert_eq!(display(21.0, Units::Imperial), " 70");
+        assert_eq!(display(21.0, Units::Standard), "294");
+        assert_eq!(display_temperature(None, Units::Metric), "  -");
+    }
+
+    #[test]
+    fn no_negative_zero() {
+        let display =
+            |celsius| display_temperature(Some(Temperature::from_celsius(celsius)), Units::Metric);
+        assert_eq!(display(-0.4), "  0");
+        assert_eq!(display(0.4), "  0");
+        assert_eq!(display(-0.5), " -1");
+    }
+
+    #[test]
+    fn min_and_max() {
+        let (cold, warm) = (
+            Temperature::from_celsius(-3.0),
+            Temperature::from_celsius(4.0),
+        );
+        assert_eq!(cold.min(warm), cold);
+        assert_eq!(cold.max(warm), warm);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("imperial".parse(), Ok(Units::Imperial));
+        assert_eq!(
+            "celsius".parse::<Units>(),
+            Err("unknown units \"celsius\"".to_string())
+        );
+        assert_eq!("both".parse(), Ok(TemperatureKind::Both));
+    }
+}
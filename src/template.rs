@@ -0,0 +1,667 @@
+use crate::geometry::Geometry;
+use crate::icon;
+use crate::locale::Locale;
+use crate::page::{display_conditions, display_temperature, PageContext, Status};
+use crate::slot::DailyValue;
+use crate::time_format::TimeFormat;
+use crate::weather::WeatherData;
+use crate::Text;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// Something a placeholder can refer to
+#[derive(Clone, Copy, Debug)]
+enum Var {
+    /// Current time
+    Now,
+    /// Status indicator of the clock row
+    Status,
+    /// Error of the last fetch
+    Error,
+    /// Time of the last successful fetch, if the data is stale
+    StaleSince,
+    /// Set while there's no data and no error yet
+    Waiting,
+    /// Set while the data is fresh
+    Fresh,
+    SlotTemperature(usize),
+    SlotLabel(usize),
+    SlotIcons(usize),
+    /// Symbol of the temperature units
+    Unit,
+    Today(DailyValue),
+    TodayIcons,
+    Sunrise,
+    Sunset,
+    Moon,
+    /// Event of the first alert in effect
+    Alert,
+}
+
+/// What a variable evaluates to
+enum Value {
+    Text(String),
+    Time(OffsetDateTime),
+    Flag(bool),
+    Missing,
+}
+
+impl Value {
+    fn is_set(&self) -> bool {
+        match self {
+            Value::Text(text) => !text.is_empty(),
+            Value::Time(_) => true,
+            Value::Flag(flag) => *flag,
+            Value::Missing => false,
+        }
+    }
+}
+
+impl Var {
+    fn parse(name: &str, slots: usize) -> anyhow::Result<Self> {
+        let var = match name {
+            "now" => Var::Now,
+            "status" => Var::Status,
+            "error" => Var::Error,
+            "stale_since" => Var::StaleSince,
+            "waiting" => Var::Waiting,
+            "fresh" => Var::Fresh,
+            "unit" => Var::Unit,
+            "today.min" => Var::Today(DailyValue::Min),
+            "today.max" => Var::Today(DailyValue::Max),
+            "today.day" => Var::Today(DailyValue::Day),
+            "today.night" => Var::Today(DailyValue::Night),
+            "today.icons" => Var::TodayIcons,
+            "sunrise" => Var::Sunrise,
+            "sunset" => Var::Sunset,
+            "moon" => Var::Moon,
+            "alert" => Var::Alert,
+            _ => {
+                let Some((index, field)) = name
+                    .strip_prefix("slot.")
+                    .and_then(|slot| slot.split_once('.'))
+                else {
+                    anyhow::bail!("unknown placeholder {{{name}}}");
+                };
+                let index = index.parse::<usize>()?;
+                if index >= slots {
+                    anyhow::bail!(
+                        "{{{name}}} refers to slot {index}, but only {slots} are configured"
+                    );
+                }
+                match field {
+                    "temp" => Var::SlotTemperature(index),
+                    "label" => Var::SlotLabel(index),
+                    "icons" => Var::SlotIcons(index),
+                    _ => anyhow::bail!(
+                        "unknown slot field in {{{name}}}, expected temp, label or icons"
+                    ),
+                }
+            }
+        };
+        Ok(var)
+    }
+
+    /// Format of time variables when the placeholder doesn't set one
+    fn default_time_format(&self) -> Option<&'static str> {
+        match self {
+            Var::Now => Some("%a %b %d %H:%M:%S"),
+            Var::StaleSince | Var::Sunrise | Var::Sunset => Some("%H:%M"),
+            _ => None,
+        }
+    }
+
+    /// Widest text the variable evaluates to, `None` for unbounded text which is cut off at the
+    /// end of the row
+    fn max_width(&self) -> Option<usize> {
+        match self {
+            Var::Status => Some(1),
+            Var::Waiting | Var::Fresh => Some(0),
+            Var::SlotTemperature(_) | Var::Today(_) => Some(3),
+            Var::SlotLabel(_) => Some(crate::slot::SLOT_WIDTH),
+            Var::SlotIcons(_) | Var::TodayIcons => Some(3),
+            Var::Unit => Some(2),
+            Var::Moon => Some(15),
+            // the width of times is given by their format
+            Var::Now | Var::StaleSince | Var::Sunrise | Var::Sunset => None,
+            Var::Error | Var::Alert => None,
+        }
+    }
+
+    fn eval(&self, ctx: &PageContext, weather_data: &WeatherData) -> Value {
+        let units = ctx.settings.units;
+        let now = ctx.now.unix_timestamp();
+        let today = ctx.today();
+        let to_time = |timestamp: Option<i64>| {
+            timestamp
+                .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+                .map_or(Value::Missing, |time| {
+                    Value::Time(ctx.settings.timezone.to_local(time))
+                })
+        };
+
+        match self {
+            Var::Now => Value::Time(ctx.now),
+            Var::Status => Value::Text(crate::page::status_indicator(ctx).to_string()),
+            Var::Error => ctx
+                .error
+                .map_or(Value::Missing, |error| Value::Text(error.to_string())),
+            Var::StaleSince => match ctx.status {
+                Status::Stale { since } => Value::Time(ctx.settings.timezone.to_local(since)),
+                _ => Value::Missing,
+            },
+            Var::Waiting => {
+                Value::Flag(matches!(ctx.status, Status::NoData) && ctx.error.is_none())
+            }
+            Var::Fresh => Value::Flag(matches!(ctx.status, Status::Fresh)),
+            Var::SlotTemperature(index) => Value::Text(
+                display_temperature(weather_data.entries[*index].temperature, units)
+                    .trim_start()
+                    .to_string(),
+            ),
+            Var::SlotLabel(index) => Value::Text(weather_data.entries[*index].label.clone()),
+            Var::SlotIcons(index) => {
+                Value::Text(display_conditions(&weather_data.entries[*index].conditions))
+            }
+            Var::Unit => Value::Text(crate::page::unit_symbol(units)),
+            Var::Today(value) => today.map_or(Value::Missing, |today| {
+                let temperature = value.of(&today.temperature);
+                Value::Text(
+                    display_temperature(Some(temperature), units)
+                        .trim_start()
+                        .to_string(),
+                )
+            }),
+            Var::TodayIcons => today.map_or(Value::Missing, |today| {
+                Value::Text(display_conditions(&today.conditions))
+            }),
+            Var::Sunrise => to_time(today.and_then(|today| today.sunrise)),
+            Var::Sunset => to_time(today.and_then(|today| today.sunset)),
+            Var::Moon => {
+                Value::Text(crate::sun_page::moon_phase(ctx.now, ctx.settings.locale).to_string())
+            }
+            Var::Alert => crate::alert_page::active_alerts(ctx.forecast, now)
+                .next()
+                .map_or(Value::Missing, |alert| Value::Text(alert.event.clone())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+/// How a placeholder is formatted
+#[derive(Clone, Debug)]
+enum Spec {
+    /// Padded and truncated to exactly `width` characters, if set
+    Text {
+        align: Align,
+        width: Option<usize>,
+    },
+    Time(TimeFormat),
+}
+
+impl Spec {
+    fn parse(var: Var, spec: Option<&str>) -> anyhow::Result<Self> {
+        if let Some(default) = var.default_time_format() {
+            return Ok(Spec::Time(spec.unwrap_or(default).parse()?));
+        }
+
+        let Some(spec) = spec else {
+            return Ok(Spec::Text {
+                align: Align::Left,
+                width: None,
+            });
+        };
+        let (align, width) = match spec.chars().next() {
+            Some('<') => (Align::Left, &spec[1..]),
+            Some('>') => (Align::Right, &spec[1..]),
+            Some('^') => (Align::Center, &spec[1..]),
+            _ => (Align::Left, spec),
+        };
+        let width = width
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid format {spec:?}, expected <N, >N, ^N or N"))?;
+        Ok(Spec::Text {
+            align,
+            width: Some(width),
+        })
+    }
+
+    fn max_width(&self, var: Var) -> Option<usize> {
+        match self {
+            Spec::Text {
+                width: Some(width), ..
+            } => Some(*width),
+            Spec::Text { width: None, .. } => var.max_width(),
+            Spec::Time(format) => Some(format.width()),
+        }
+    }
+
+    fn format(&self, value: &Value, locale: Locale, out: &mut String) {
+        let text = match (self, value) {
+            (Spec::Time(format), Value::Time(time)) => format.format(*time, locale),
+            // keep the columns after a missing time in place
+            (Spec::Time(format), _) => format!("{:<width$}", "-", width = format.width()),
+            (_, Value::Text(text)) => text.clone(),
+            (_, Value::Flag(_)) => String::new(),
+            (_, Value::Time(_)) | (_, Value::Missing) => "-".to_string(),
+        };
+        match self {
+            Spec::Text {
+                align,
+                width: Some(width),
+            } => {
+                // the width is in display cells, some characters take more than one
+                let text = icon::truncate(&text, *width);
+                let fill = width - icon::width(text);
+                let (before, after) = match align {
+                    Align::Left => (0, fill),
+                    Align::Right => (fill, 0),
+                    Align::Center => (fill / 2, fill - fill / 2),
+                };
+                out.push_str(&" ".repeat(before));
+                out.push_str(text);
+                out.push_str(&" ".repeat(after));
+            }
+            _ => out.push_str(&text),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Literal(String),
+    Placeholder {
+        var: Var,
+        spec: Spec,
+    },
+    If {
+        var: Var,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// How often the current time shown by `nodes` changes, `None` if it isn't shown
+fn resolution(nodes: &[Node]) -> Option<Duration> {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Placeholder {
+                var: Var::Now,
+                spec: Spec::Time(format),
+            } => Some(format.resolution()),
+            Node::If {
+                then, otherwise, ..
+            } => resolution(then)
+                .into_iter()
+                .chain(resolution(otherwise))
+                .min(),
+            _ => None,
+        })
+        .min()
+}
+
+/// Widest text of `nodes`, counting unbounded placeholders as empty
+fn max_width(nodes: &[Node]) -> usize {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Literal(text) => icon::width(text),
+            Node::Placeholder { var, spec } => spec.max_width(*var).unwrap_or(0),
+            Node::If {
+                then, otherwise, ..
+            } => max_width(then).max(max_width(otherwise)),
+        })
+        .sum()
+}
+
+fn render(nodes: &[Node], ctx: &PageContext, weather_data: &WeatherData, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Literal(text) => out.push_str(text),
+            Node::Placeholder { var, spec } => {
+                spec.format(&var.eval(ctx, weather_data), ctx.settings.locale, out)
+            }
+            Node::If {
+                var,
+                negate,
+                then,
+                otherwise,
+            } => {
+                let branch = match var.eval(ctx, weather_data).is_set() != *negate {
+                    true => then,
+                    false => otherwise,
+                };
+                render(branch, ctx, weather_data, out);
+            }
+        }
+    }
+}
+
+/// Tag of a template, the part between braces
+enum Tag<'a> {
+    Placeholder(&'a str, Option<&'a str>),
+    If(&'a str, bool),
+    Else,
+    End,
+}
+
+impl<'a> Tag<'a> {
+    fn parse(tag: &'a str) -> Self {
+        match tag.trim() {
+            "else" => Tag::Else,
+            "end" => Tag::End,
+            tag => match tag.strip_prefix("if ") {
+                Some(condition) => match condition.trim().strip_prefix('!') {
+                    Some(name) => Tag::If(name.trim(), true),
+                    None => Tag::If(condition.trim(), false),
+                },
+                None => match tag.split_once(':') {
+                    Some((name, spec)) => Tag::Placeholder(name.trim(), Some(spec)),
+                    None => Tag::Placeholder(tag, None),
+                },
+            },
+        }
+    }
+}
+
+/// Parse nodes up to the `else` or `end` tag closing them, which is returned as well
+fn parse_nodes<'a>(s: &mut &'a str, slots: usize) -> anyhow::Result<(Vec<Node>, Option<Tag<'a>>)> {
+    let mut nodes = Vec::new();
+    let mut literal = String::new();
+    loop {
+        let Some(i) = s.find(['{', '}']) else {
+            literal.push_str(s);
+            *s = "";
+            break;
+        };
+        literal.push_str(&s[..i]);
+        let rest = &s[i..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            literal.push_str(&rest[..1]);
+            *s = &rest[2..];
+            continue;
+        }
+        if rest.starts_with('}') {
+            anyhow::bail!("unmatched }}, write }}}} for a literal one");
+        }
+        let Some(end) = rest.find('}') else {
+            anyhow::bail!("unclosed {{, write {{{{ for a literal one");
+        };
+        *s = &rest[end + 1..];
+
+        if !literal.is_empty() {
+            nodes.push(Node::Literal(std::mem::take(&mut literal)));
+        }
+        match Tag::parse(&rest[1..end]) {
+            Tag::Placeholder(name, spec) => {
+                let var = Var::parse(name, slots)?;
+                let spec = Spec::parse(var, spec)?;
+                nodes.push(Node::Placeholder { var, spec });
+            }
+            Tag::If(name, negate) => {
+                let var = Var::parse(name, slots)?;
+                let (then, closing) = parse_nodes(s, slots)?;
+                let otherwise = match closing {
+                    Some(Tag::Else) => match parse_nodes(s, slots)? {
+                        (otherwise, Some(Tag::End)) => otherwise,
+                        _ => anyhow::bail!("{{if {name}}} isn't closed by {{end}}"),
+                    },
+                    Some(Tag::End) => Vec::new(),
+                    _ => anyhow::bail!("{{if {name}}} isn't closed by {{end}}"),
+                };
+                nodes.push(Node::If {
+                    var,
+                    negate,
+                    then,
+                    otherwise,
+                });
+            }
+            tag @ (Tag::Else | Tag::End) => return Ok((nodes, Some(tag))),
+        }
+    }
+    if !literal.is_empty() {
+        nodes.push(Node::Literal(literal));
+    }
+    Ok((nodes, None))
+}
+
+/// Layout of a screen compiled from rows of text with placeholders, such as
+/// `{now:%H:%M} {if fresh}{slot.0.temp:>3}{unit}{else}{status}{end}`
+#[derive(Clone, Debug)]
+pub struct Template {
+    rows: Vec<Vec<Node>>,
+}
+
+impl Template {
+    /// Compile `s`, one row per line, for a display of `geometry` showing `slots` forecast slots
+    pub fn compile(s: &str, slots: usize, geometry: Geometry) -> anyhow::Result<Self> {
+        let lines = s.lines().collect::<Vec<_>>();
+        if lines.len() > geometry.rows {
+            anyhow::bail!(
+                "the layout has {} rows, only {} fit",
+                lines.len(),
+                geometry.rows
+            );
+        }
+
+        let mut rows = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let mut rest = *line;
+            let nodes = match parse_nodes(&mut rest, slots) {
+                Ok((nodes, None)) => nodes,
+                Ok((_, Some(Tag::Else))) => anyhow::bail!("row {}: {{else}} without {{if}}", i + 1),
+                Ok((_, Some(_))) => anyhow::bail!("row {}: {{end}} without {{if}}", i + 1),
+                Err(e) => return Err(e.context(format!("row {}", i + 1))),
+            };
+            let width = max_width(&nodes);
+            if width > geometry.cols {
+                anyhow::bail!(
+                    "row {} is up to {width} cells wide, only {} fit",
+                    i + 1,
+                    geometry.cols
+                );
+            }
+            rows.push(nodes);
+        }
+        Ok(Self { rows })
+    }
+
+    /// How often the current time shown by the layout changes, `None` if it isn't shown
+    pub fn resolution(&self) -> Option<Duration> {
+        self.rows.iter().filter_map(|nodes| resolution(nodes)).min()
+    }
+
+    pub fn render(&self, ctx: &PageContext) -> Text {
+        let weather_data = WeatherData::at(
+            ctx.forecast,
+            &ctx.settings.slots,
+            ctx.now,
+            &ctx.settings.timezone,
+            ctx.alternate(),
+        );
+        let mut text = Text::new(ctx.settings.geometry);
+        for (i, nodes) in self.rows.iter().enumerate() {
+            let mut out = String::new();
+            render(nodes, ctx, &weather_data, &mut out);
+            text.set_row(i, &out);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::icon::Icon;
+    use crate::source::tests::settings;
+    use crate::temperature::Temperature;
+    use crate::weather::{Alert, Forecast, Sample};
+    use time::macros::datetime;
+
+    /// Wednesday 18 March 2026, 14:05:30 in Prague
+    const NOW: OffsetDateTime = datetime!(2026-03-18 13:05:30 UTC);
+
+    fn compile(s: &str) -> anyhow::Result<Template> {
+        Template::compile(s, 2, Geometry::LCD_20X4)
+    }
+
+    /// Full chain of the error compiling `s`
+    fn error(s: &str) -> String {
+        format!("{:#}", compile(s).unwrap_err())
+    }
+
+    /// The first row of `s` rendered at `NOW` with fresh data and, if set, an alert in effect
+    fn render_with(s: &str, status: Status, alert: Option<&str>) -> String {
+        let settings = settings(Geometry::LCD_20X4, "forecast", "now,+1h", None);
+        let forecast = Forecast {
+            current: Sample {
+                dt: NOW.unix_timestamp(),
+                temperature: Temperature::from_celsius(10.4),
+                ..Default::default()
+            },
+            alerts: alert
+                .map(|event| Alert {
+                    event: event.to_string(),
+                    start: NOW.unix_timestamp() - 3600,
+                    end: NOW.unix_timestamp() + 3600,
+                })
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let ctx = PageContext {
+            now: settings.timezone.to_local(NOW),
+            forecast: &forecast,
+            status,
+            error: None,
+            settings: &settings,
+            frame: 0,
+        };
+        let text = compile(s).unwrap().render(&ctx);
+        text.rows[0].as_str().to_string()
+    }
+
+    fn render(s: &str) -> String {
+        render_with(s, Status::Fresh, None)
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(
+            render("{now:%H:%M} {slot.0.temp}{unit}"),
+            format!("14:05 10{}C", Icon::Degree.to_char())
+        );
+        assert_eq!(render("{slot.0.label}/{slot.1.label}"), "now/+1h");
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(render("{{now}} }}{{"), "{now} }{");
+        assert_eq!(
+            error("a } b"),
+            "row 1: unmatched }, write }} for a literal one"
+        );
+        assert_eq!(
+            error("a { b"),
+            "row 1: unclosed {, write {{ for a literal one"
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        let s = "{if fresh}{if alert}{alert}{else}calm{end}{else}{if !waiting}stale{end}{end}";
+        assert_eq!(render(s), "calm");
+        assert_eq!(render_with(s, Status::Fresh, Some("Fog")), "Fog");
+        assert_eq!(render_with(s, Status::Stale { since: NOW }, None), "stale");
+        assert_eq!(render_with(s, Status::NoData, None), "");
+    }
+
+    #[test]
+    fn unbalanced_conditions() {
+        assert_eq!(error("a{else}b"), "row 1: {else} without {if}");
+        assert_eq!(error("a{end}"), "row 1: {end} without {if}");
+        assert_eq!(
+            error("{if fresh}a"),
+            "row 1: {if fresh} isn't closed by {end}"
+        );
+        assert_eq!(
+            error("{if fresh}a{else}b"),
+            "row 1: {if fresh} isn't closed by {end}"
+        );
+        assert_eq!(
+            error("ok\n{if fresh}{if alert}a{end}"),
+            "row 2: {if fresh} isn't closed by {end}"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholders() {
+        assert_eq!(
+            error("{temperature}"),
+            "row 1: unknown placeholder {temperature}"
+        );
+        assert_eq!(
+            error("{if sunny}x{end}"),
+            "row 1: unknown placeholder {sunny}"
+        );
+        assert_eq!(
+            error("{slot.0.wind}"),
+            "row 1: unknown slot field in {slot.0.wind}, expected temp, label or icons"
+        );
+    }
+
+    #[test]
+    fn slot_out_of_range() {
+        assert_eq!(
+            error("{slot.2.temp}"),
+            "row 1: {slot.2.temp} refers to slot 2, but only 2 are configured"
+        );
+    }
+
+    #[test]
+    fn alignment() {
+        assert_eq!(
+            render("[{slot.0.label:<4}][{slot.0.label:>4}][{slot.0.label:^6}]"),
+            "[now ][ now][ now  ]"
+        );
+        assert_eq!(render("[{slot.0.label:4}][{slot.1.label:2}]"), "[now ][+1]");
+        assert_eq!(
+            error("{slot.0.label:x5}"),
+            "row 1: invalid format \"x5\", expected <N, >N, ^N or N"
+        );
+    }
+
+    #[test]
+    fn missing_time_keeps_the_columns() {
+        assert_eq!(render("{sunrise}|{sunset:%H}|"), "-    |- |");
+    }
+
+    #[test]
+    fn too_wide() {
+        assert_eq!(
+            error("{now} {slot.0.temp}"),
+            "row 1 is up to 23 cells wide, only 20 fit"
+        );
+        // the wider branch of a condition counts
+        assert_eq!(
+            error("{if fresh}x{else}{moon}{now:%H:%M:%S}{end}"),
+            "row 1 is up to 23 cells wide, only 20 fit"
+        );
+        assert!(compile("{now:%H:%M} {alert}").is_ok());
+        assert_eq!(
+            format!(
+                "{:#}",
+                Template::compile("a\nb\nc", 2, "20x2".parse().unwrap()).unwrap_err()
+            ),
+            "the layout has 3 rows, only 2 fit"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::icon;
+use crate::locale::Locale;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// A piece of a `strftime`-like time format
+#[derive(Clone, Debug)]
+enum TimeItem {
+    Literal(char),
+    /// `%a`, such as Mon, padded to three characters
+    Weekday,
+    /// `%b`, such as Jan, padded to three characters
+    Month,
+    /// `%m`
+    MonthNumber,
+    /// `%d`, zero-padded
+    Day,
+    /// `%e`, space-padded
+    DaySpace,
+    /// `%j`
+    DayOfYear,
+    /// `%V`, ISO 8601 week number
+    Week,
+    /// `%Y`
+    Year,
+    /// `%y`
+    YearShort,
+    /// `%H`
+    Hour,
+    /// `%I`
+    Hour12,
+    /// `%p`, AM or PM
+    AmPm,
+    /// `%M`
+    Minute,
+    /// `%S`
+    Second,
+}
+
+impl TimeItem {
+    fn width(&self) -> usize {
+        match self {
+            TimeItem::Literal(c) => icon::cells(*c),
+            TimeItem::Weekday | TimeItem::Month | TimeItem::DayOfYear => 3,
+            TimeItem::Year => 4,
+            _ => 2,
+        }
+    }
+
+    /// How often the formatted field changes
+    fn resolution(&self) -> Duration {
+        match self {
+            TimeItem::Literal(_) => Duration::MAX,
+            TimeItem::Second => Duration::from_secs(1),
+            TimeItem::Minute => Duration::from_secs(60),
+            TimeItem::Hour | TimeItem::Hour12 | TimeItem::AmPm => Duration::from_secs(3600),
+            _ => Duration::from_secs(86400),
+        }
+    }
+
+    fn format(&self, time: OffsetDateTime, locale: Locale, out: &mut String) {
+        use std::fmt::Write;
+
+        let _ = match self {
+            TimeItem::Literal(c) => write!(out, "{c}"),
+            TimeItem::Weekday => write!(out, "{}", icon::pad(locale.weekday(time.weekday()), 3)),
+            TimeItem::Month => write!(out, "{}", icon::pad(locale.month(time.month()), 3)),
+            TimeItem::MonthNumber => write!(out, "{:02}", time.month() as u8),
+            TimeItem::Day => write!(out, "{:02}", time.day()),
+            TimeItem::DaySpace => write!(out, "{:2}", time.day()),
+            TimeItem::DayOfYear => write!(out, "{:03}", time.ordinal()),
+            TimeItem::Week => write!(out, "{:02}", time.iso_week()),
+            TimeItem::Year => write!(out, "{:04}", time.year()),
+            TimeItem::YearShort => write!(out, "{:02}", time.year().rem_euclid(100)),
+            TimeItem::Hour => write!(out, "{:02}", time.hour()),
+            TimeItem::Hour12 => write!(out, "{:02}", (time.hour() + 11) % 12 + 1),
+            TimeItem::AmPm => write!(out, "{}", if time.hour() < 12 { "AM" } else { "PM" }),
+            TimeItem::Minute => write!(out, "{:02}", time.minute()),
+            TimeItem::Second => write!(out, "{:02}", time.second()),
+        };
+    }
+}
+
+/// Time format made of `strftime`-like fields, all of which have a fixed width
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TimeFormat {
+    items: Vec<TimeItem>,
+}
+
+impl TimeFormat {
+    /// Number of characters of the formatted time
+    pub fn width(&self) -> usize {
+        self.items.iter().map(TimeItem::width).sum()
+    }
+
+    /// How often the formatted time changes, given by its finest field
+    pub fn resolution(&self) -> Duration {
+        self.items
+            .iter()
+            .map(TimeItem::resolution)
+            .min()
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Format `time` with the names of `locale`
+    pub fn format(&self, time: OffsetDateTime, locale: Locale) -> String {
+        let mut out = String::new();
+        for item in &self.items {
+            item.format(time, locale, &mut out);
+        }
+        out
+    }
+}
+
+impl FromStr for TimeFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut items = Vec::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                items.push(TimeItem::Literal(c));
+                continue;
+            }
+            let item = match chars.next() {
+                Some('a') => TimeItem::Weekday,
+                Some('b') => TimeItem::Month,
+                Some('m') => TimeItem::MonthNumber,
+                Some('d') => TimeItem::Day,
+                Some('e') => TimeItem::DaySpace,
+                Some('j') => TimeItem::DayOfYear,
+                Some('V') => TimeItem::Week,
+                Some('Y') => TimeItem::Year,
+                Some('y') => TimeItem::YearShort,
+                Some('H') => TimeItem::Hour,
+                Some('I') => TimeItem::Hour12,
+                Some('p') => TimeItem::AmPm,
+                Some('M') => TimeItem::Minute,
+                Some('S') => TimeItem::Second,
+                Some('%') => TimeItem::Literal('%'),
+                Some(c) => anyhow::bail!("unknown time field %{c} in {s:?}"),
+                None => anyhow::bail!("time format {s:?} ends with a lone %"),
+            };
+            items.push(item);
+        }
+        Ok(Self { items })
+    }
+}
+
+impl TryFrom<String> for TimeFormat {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> anyhow::Result<Self> {
+        s.parse()
+    }
+}
+
+impl configure_me::parse_arg::ParseArgFromStr for TimeFormat {
+    fn describe_type<W: std::fmt::Write>(mut writer: W) -> std::fmt::Result {
+        write!(writer, "a strftime-like time format")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::datetime;
+
+    fn format(format: &str, time: OffsetDateTime) -> String {
+        format
+            .parse::<TimeFormat>()
+            .unwrap()
+            .format(time, Locale::English)
+    }
+
+    #[test]
+    fn twelve_hour_clock() {
+        assert_eq!(
+            format("%I:%M %p", datetime!(2026-03-18 00:05 UTC)),
+            "12:05 AM"
+        );
+        assert_eq!(
+            format("%I:%M %p", datetime!(2026-03-18 01:00 UTC)),
+            "01:00 AM"
+        );
+        assert_eq!(
+            format("%I:%M %p", datetime!(2026-03-18 11:59 UTC)),
+            "11:59 AM"
+        );
+        assert_eq!(
+            format("%I:%M %p", datetime!(2026-03-18 12:00 UTC)),
+            "12:00 PM"
+        );
+        assert_eq!(
+            format("%I:%M %p", datetime!(2026-03-18 12:30 UTC)),
+            "12:30 PM"
+        );
+        assert_eq!(
+            format("%I:%M %p", datetime!(2026-03-18 23:59 UTC)),
+            "11:59 PM"
+        );
+    }
+
+    #[test]
+    fn iso_week_around_the_new_year() {
+        // 2026 starts on a Thursday and has 53 weeks
+        assert_eq!(format("%V", datetime!(2025-12-28 12:00 UTC)), "52");
+        assert_eq!(format("%V", datetime!(2025-12-29 12:00 UTC)), "01");
+        assert_eq!(format("%V", datetime!(2026-01-01 12:00 UTC)), "01");
+        assert_eq!(format("%V", datetime!(2026-12-31 12:00 UTC)), "53");
+        assert_eq!(format("%V", datetime!(2027-01-03 12:00 UTC)), "53");
+        assert_eq!(format("%V", datetime!(2027-01-04 12:00 UTC)), "01");
+    }
+
+    #[test]
+    fn day_of_year() {
+        assert_eq!(format("%j", datetime!(2026-01-01 12:00 UTC)), "001");
+        assert_eq!(format("%j", datetime!(2026-03-18 12:00 UTC)), "077");
+        assert_eq!(format("%j", datetime!(2024-12-31 12:00 UTC)), "366");
+    }
+
+    #[test]
+    fn padding() {
+        let time = datetime!(2006-03-05 04:03:02 UTC);
+        assert_eq!(format("%d|%e|%m|%y|%Y", time), "05| 5|03|06|2006");
+        assert_eq!(format("%H:%M:%S", time), "04:03:02");
+        assert_eq!(format("100%% %a", time), "100% Sun");
+        let czech = "%a %b"
+            .parse::<TimeFormat>()
+            .unwrap()
+            .format(time, Locale::Czech);
+        assert_eq!(czech, "ne  bře");
+    }
+
+    #[test]
+    fn width() {
+        let width = |format: &str| format.parse::<TimeFormat>().unwrap().width();
+        assert_eq!(width("%a %b %d %H:%M:%S"), 19);
+        assert_eq!(width("%j %V %Y %y"), 14);
+        assert_eq!(width("%I%p"), 4);
+        // ß is shown as ss
+        assert_eq!(width("ß%H"), 4);
+        assert_eq!(width(""), 0);
+    }
+
+    #[test]
+    fn resolution() {
+        let resolution = |format: &str| format.parse::<TimeFormat>().unwrap().resolution();
+        assert_eq!(resolution("%H:%M:%S"), Duration::from_secs(1));
+        assert_eq!(resolution("%a %H:%M"), Duration::from_secs(60));
+        assert_eq!(resolution("%I %p"), Duration::from_secs(3600));
+        assert_eq!(resolution("%a %d %V"), Duration::from_secs(86400));
+        assert_eq!(resolution("clock"), Duration::MAX);
+    }
+
+    #[test]
+    fn invalid_formats() {
+        let error = |format: &str| format.parse::<TimeFormat>().unwrap_err().to_string();
+        assert_eq!(error("%H:%Q"), "unknown time field %Q in \"%H:%Q\"");
+        assert_eq!(error("%H%"), "time format \"%H%\" ends with a lone %");
+    }
+}
@@ -0,0 +1,130 @@
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use tz::TimeZone;
+
+/// Time zone of the clock, including its daylight saving time rules
+#[derive(Clone, Debug)]
+pub struct LocalTimeZone {
+    tz: TimeZone,
+}
+
+impl LocalTimeZone {
+    /// Load the IANA time zone `name`, such as `Europe/Prague`, from the system time zone database.
+    /// Without a name, the system time zone is used.
+    pub fn load(name: Option<&str>) -> anyhow::Result<Self> {
+        let tz = match name {
+            Some(name) => TimeZone::from_posix_tz(name)
+                .map_err(|e| anyhow::anyhow!("failed to load time zone {name}: {e}"))?,
+            None => TimeZone::local()
+                .map_err(|e| anyhow::anyhow!("failed to load the system time zone: {e}"))?,
+        };
+        Ok(Self { tz })
+    }
+
+    /// UTC offset in effect at `time`
+    pub fn offset_at(&self, time: OffsetDateTime) -> UtcOffset {
+        self.tz
+            .find_local_time_type(time.unix_timestamp())
+            .ok()
+            .and_then(|ltt| UtcOffset::from_whole_seconds(ltt.ut_offset()).ok())
+            .unwrap_or(UtcOffset::UTC)
+    }
+
+    /// Convert `time` to the local time
+    pub fn to_local(&self, time: OffsetDateTime) -> OffsetDateTime {
+        time.to_offset(self.offset_at(time))
+    }
+
+    /// Find the instant a local date and time refers to. When the clocks go back and the local
+    /// time occurs twice, the first occurrence is used. When the clocks go forward and the local
+    /// time is skipped, it's moved forward by the length of the gap.
+    pub fn resolve(&self, local: PrimitiveDateTime) -> OffsetDateTime {
+        let before = self.offset_at(local.assume_utc() - Duration::DAY);
+        let after = self.offset_at(local.assume_utc() + Duration::DAY);
+
+        // the offset before a transition gives the earlier of two candidate instants
+        for offset in [before, after] {
+            let time = local.assume_offset(offset);
+            if self.offset_at(time) == offset {
+                return time;
+            }
+        }
+
+        self.to_local(local.assume_offset(before))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::tests::PRAGUE;
+    use time::macros::{datetime, offset};
+
+    fn prague() -> LocalTimeZone {
+        LocalTimeZone::load(Some(PRAGUE)).unwrap()
+    }
+
+    #[test]
+    fn offsets_around_the_transitions() {
+        let tz = prague();
+        assert_eq!(
+            tz.offset_at(datetime!(2026-03-29 00:59:59 UTC)),
+            offset!(+1)
+        );
+        assert_eq!(
+            tz.offset_at(datetime!(2026-03-29 01:00:00 UTC)),
+            offset!(+2)
+        );
+        assert_eq!(
+            tz.offset_at(datetime!(2026-10-25 00:59:59 UTC)),
+            offset!(+2)
+        );
+        assert_eq!(
+            tz.offset_at(datetime!(2026-10-25 01:00:00 UTC)),
+            offset!(+1)
+        );
+    }
+
+    #[test]
+    fn resolve_ordinary_time() {
+        let tz = prague();
+        assert_eq!(
+            tz.resolve(datetime!(2026-01-15 08:00)),
+            datetime!(2026-01-15 08:00 +1)
+        );
+        assert_eq!(
+            tz.resolve(datetime!(2026-07-15 08:00)),
+            datetime!(2026-07-15 08:00 +2)
+        );
+    }
+
+    #[test]
+    fn resolve_skipped_time() {
+        // 02:30 doesn't exist when the clocks go forward from 02:00 to 03:00
+        assert_eq!(
+            prague().resolve(datetime!(2026-03-29 02:30)),
+            datetime!(2026-03-29 03:30 +2)
+        );
+    }
+
+    #[test]
+    fn resolve_repeated_time() {
+        // 02:30 occurs twice when the clocks go back from 03:00 to 02:00, the first one is used
+        assert_eq!(
+            prague().resolve(datetime!(2026-10-25 02:30)),
+            datetime!(2026-10-25 02:30 +2)
+        );
+    }
+
+    #[test]
+    fn resolve_midnights_around_the_transitions() {
+        let tz = prague();
+        assert_eq!(
+            tz.resolve(datetime!(2026-03-30 00:00)),
+            datetime!(2026-03-29 22:00 UTC)
+        );
+        assert_eq!(
+            tz.resolve(datetime!(2026-10-26 00:00)),
+            datetime!(2026-10-25 23:00 UTC)
+        );
+    }
+}
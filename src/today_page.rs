@@ -0,0 +1,69 @@
+use crate::page::{
+    arrange, clock_row, display_conditions, display_temperature, fit, Page, PageContext,
+};
+use crate::slot::Slot;
+use crate::Text;
+
+/// Today's temperatures and conditions
+#[derive(Debug)]
+pub struct TodayPage;
+
+impl Page for TodayPage {
+    fn render(&self, ctx: &PageContext) -> Option<Text> {
+        let today = ctx.today()?;
+        let units = ctx.settings.units;
+        let temperature = |t| display_temperature(Some(t), units);
+
+        // the current temperature is the kind shown in the slots
+        let feels_like = ctx.settings.temperature.shows_feels_like(ctx.alternate());
+        let now = Slot::Now
+            .select(ctx.forecast, ctx.now, &ctx.settings.timezone, feels_like)
+            .map(|(t, _)| t);
+        let phrases = ctx.settings.locale.phrases();
+        let (today_label, now_label) = (phrases.today, phrases.now);
+        let conditions = display_conditions(&today.conditions);
+        let now = display_temperature(now, units);
+        let summary = fit(
+            ctx,
+            &[
+                format!("{today_label} {conditions}  {now_label} {now}"),
+                format!(
+                    "{today_label} {conditions} {now_label} {}",
+                    now.trim_start()
+                ),
+                format!("{conditions} {now_label} {}", now.trim_start()),
+            ],
+        );
+        let (low, high) = (
+            temperature(today.temperature.min),
+            temperature(today.temperature.max),
+        );
+        let (low_label, high_label) = (phrases.low, phrases.high);
+        let range = fit(
+            ctx,
+            &[
+                format!("{low_label} {low}  {high_label} {high}"),
+                format!(
+                    "{low_label} {} {high_label} {}",
+                    low.trim_start(),
+                    high.trim_start()
+                ),
+            ],
+        );
+        let (day, night) = (
+            temperature(today.feels_like.day),
+            temperature(today.feels_like.night),
+        );
+        let feels = phrases.feels;
+        let feels_like = fit(
+            ctx,
+            &[
+                format!("{feels} {day} / {night}"),
+                format!("{feels} {} / {}", day.trim_start(), night.trim_start()),
+                format!("{feels} {}/{}", day.trim_start(), night.trim_start()),
+            ],
+        );
+
+        Some(arrange(ctx, &[clock_row(ctx), summary, range, feels_like]))
+    }
+}
@@ -1,124 +1,272 @@
+use crate::cache::{CachedForecast, ForecastCache};
+use crate::condition::Conditions;
+use crate::provider::WeatherProvider;
+use crate::slot::SlotSpec;
+use crate::temperature::Temperature;
+use crate::timezone::LocalTimeZone;
 use actix::prelude::*;
-use std::sync::Arc;
-use std::time::Duration;
-
-#[derive(Default, Copy, Clone)]
-pub struct RainSituation {
-    pub rain: bool,
-    pub snow: bool,
-    pub thunder: bool,
-}
-
-impl<'a, I: Iterator<Item = &'a crate::owm::Weather>> From<I> for RainSituation {
-    fn from(mut i: I) -> Self {
-        let rain = i.any(|w| w.id / 100 == 5 || w.id / 100 == 2 || w.id / 100 == 3);
-        let thunder = i.any(|w| w.id / 100 == 2);
-        let snow = i.any(|w| w.id / 100 == 6);
-        RainSituation {
-            rain,
-            thunder,
-            snow,
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use time::{Date, OffsetDateTime};
+
+/// Temperature and conditions of one slot. The temperature is missing when the forecast doesn't
+/// cover the slot.
+#[derive(Default, Clone)]
+pub struct WeatherEntry {
+    pub temperature: Option<Temperature>,
+    pub conditions: Conditions,
+    pub label: String,
+}
+
+/// Conditions at a single point in time, as reported by any provider
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct Sample {
+    /// Unix timestamp of the observation, or of the start of the forecast hour
+    pub dt: i64,
+    pub temperature: Temperature,
+    pub feels_like: Temperature,
+    pub conditions: Conditions,
+    #[serde(default)]
+    pub details: Details,
+}
+
+/// Measurements besides the temperature and conditions, each missing if the provider doesn't
+/// report it
+#[derive(Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Details {
+    /// Wind speed in m/s
+    pub wind_speed: Option<f32>,
+    /// Speed of wind gusts in m/s
+    pub wind_gust: Option<f32>,
+    /// Direction the wind blows from, in degrees clockwise from north
+    pub wind_direction: Option<f32>,
+    /// Relative humidity in percent
+    pub humidity: Option<f32>,
+    /// Sea-level pressure in hPa
+    pub pressure: Option<f32>,
+    pub dew_point: Option<Temperature>,
+    pub uv_index: Option<f32>,
+    /// Cloud cover in percent
+    pub clouds: Option<f32>,
+    /// Visibility in meters
+    pub visibility: Option<f32>,
+}
+
+/// Temperatures over a whole day
+#[derive(Default, Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct DailyTemperatures {
+    pub min: Temperature,
+    pub max: Temperature,
+    pub day: Temperature,
+    pub night: Temperature,
+}
+
+impl DailyTemperatures {
+    /// Temperatures of a day with a known range only, using its ends as the daytime and nighttime
+    /// temperatures
+    pub fn from_range(min: Temperature, max: Temperature) -> Self {
+        Self {
+            min,
+            max,
+            day: max,
+            night: min,
         }
     }
+
+    /// Include `temperature` in the temperatures of the day, using the warmest and coldest ones as
+    /// the daytime and nighttime ones
+    fn include(&mut self, temperature: Temperature) {
+        self.min = self.min.min(temperature);
+        self.max = self.max.max(temperature);
+        self.day = self.max;
+        self.night = self.min;
+    }
 }
 
-#[derive(Clone)]
-pub struct WeatherEntry {
-    pub temperature: i8,
-    pub rain_situation: RainSituation,
-    pub label: &'static str,
+/// Conditions over a whole day, as reported by any provider
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct DailySample {
+    /// Unix timestamp of the local midnight starting the day
+    pub dt: i64,
+    pub temperature: DailyTemperatures,
+    pub feels_like: DailyTemperatures,
+    pub conditions: Conditions,
+    /// Unix timestamps of the sunrise and sunset, if the provider reports them
+    pub sunrise: Option<i64>,
+    pub sunset: Option<i64>,
+    /// Details of the whole day, only reported by some providers
+    #[serde(default)]
+    pub details: Details,
 }
 
-impl Default for WeatherEntry {
-    fn default() -> Self {
-        Self {
-            temperature: -17,
-            rain_situation: RainSituation {
-                rain: true,
-                snow: true,
-                thunder: true,
-            },
-            label: "",
+impl DailySample {
+    /// Aggregate samples of consecutive dates into one sample per date, for providers which don't
+    /// report daily summaries. The warmest and coldest temperatures are used for the daytime and
+    /// nighttime ones.
+    pub fn aggregate(samples: &[(OffsetDateTime, Sample)]) -> Vec<Self> {
+        let mut daily: Vec<(time::Date, Self)> = Vec::new();
+        for (time, sample) in samples {
+            match daily.last_mut() {
+                Some((date, day)) if *date == time.date() => {
+                    day.temperature.include(sample.temperature);
+                    day.feels_like.include(sample.feels_like);
+                    day.conditions = day.conditions.merge(sample.conditions);
+                }
+                _ => daily.push((
+                    time.date(),
+                    Self {
+                        dt: time.replace_time(time::Time::MIDNIGHT).unix_timestamp(),
+                        temperature: DailyTemperatures::from_range(
+                            sample.temperature,
+                            sample.temperature,
+                        ),
+                        feels_like: DailyTemperatures::from_range(
+                            sample.feels_like,
+                            sample.feels_like,
+                        ),
+                        conditions: sample.conditions,
+                        sunrise: None,
+                        sunset: None,
+                        details: Details::default(),
+                    },
+                )),
+            }
         }
+        daily.into_iter().map(|(_, day)| day).collect()
     }
 }
 
-#[derive(Message, Clone)]
-#[rtype(result = "()")]
-pub struct WeatherData {
-    pub entries: [WeatherEntry; 5],
+/// A weather warning issued for the location
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct Alert {
+    pub event: String,
+    /// Unix timestamps of the period the alert is in effect
+    pub start: i64,
+    pub end: i64,
 }
 
-impl From<crate::owm::Root> for WeatherData {
-    fn from(owm: crate::owm::Root) -> Self {
-        let current = WeatherEntry {
-            temperature: owm.current.feels_like.round() as i8,
-            rain_situation: RainSituation::from(owm.current.weather.iter()),
-            label: "now",
-        };
+/// Length in seconds of the day the clocks go back
+const LONGEST_DAY: i64 = 25 * 3600;
 
-        let hour_1 = &owm.hourly[2];
-        let hour_1 = WeatherEntry {
-            temperature: hour_1.feels_like.round() as i8,
-            rain_situation: RainSituation::from(hour_1.weather.iter()),
-            label: "+2h",
-        };
-        let hour_2 = &owm.hourly[4];
-        let hour_2 = WeatherEntry {
-            temperature: hour_2.feels_like.round() as i8,
-            rain_situation: RainSituation::from(hour_2.weather.iter()),
-            label: "+4h",
-        };
-        let hour_3 = &owm.hourly[6];
-        let hour_3 = WeatherEntry {
-            temperature: hour_3.feels_like.round() as i8,
-            rain_situation: RainSituation::from(hour_3.weather.iter()),
-            label: "+6h",
-        };
+/// Provider-neutral forecast, ordered by time
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct Forecast {
+    pub current: Sample,
+    pub hourly: Vec<Sample>,
+    pub daily: Vec<DailySample>,
+    pub alerts: Vec<Alert>,
+}
 
-        // daily[0] is today
-        let tomorrow = &owm.daily[1];
-        let tomorrow = WeatherEntry {
-            temperature: tomorrow.feels_like.day.round() as i8,
-            rain_situation: RainSituation::from(tomorrow.weather.iter()),
-            label: "tmr",
-        };
+impl Forecast {
+    /// The hourly sample covering unix timestamp `time`
+    pub fn hour(&self, time: i64) -> Option<&Sample> {
+        self.hourly
+            .iter()
+            .rev()
+            .find(|sample| sample.dt <= time)
+            .filter(|sample| time < sample.dt + 3600)
+    }
 
-        Self {
-            entries: [current.clone(), hour_1, hour_2, hour_3, tomorrow],
+    /// The weather at unix timestamp `time`. The current conditions are more precise than the
+    /// forecast, so they're used while they're fresh.
+    pub fn current_at(&self, time: i64) -> Option<&Sample> {
+        match time < self.current.dt + 3600 {
+            true => Some(&self.current),
+            false => self.hour(time),
         }
     }
+
+    /// The daily sample covering unix timestamp `time`. Days last until the start of the next
+    /// one, as they're 23 or 25 hours long when the clocks change.
+    pub fn day(&self, time: i64) -> Option<&DailySample> {
+        let i = self.daily.iter().rposition(|sample| sample.dt <= time)?;
+        let end = match self.daily.get(i + 1) {
+            Some(next) => next.dt,
+            None => self.daily[i].dt + LONGEST_DAY,
+        };
+        Some(&self.daily[i]).filter(|_| time < end)
+    }
+
+    /// The daily sample of the local `date`
+    pub fn date(&self, date: Date, tz: &LocalTimeZone) -> Option<&DailySample> {
+        // the middle of the day, the days of some providers start an hour off after the clocks
+        // change
+        self.day(
+            tz.resolve(date.with_time(time::macros::time!(12:00)))
+                .unix_timestamp(),
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct WeatherData {
+    pub entries: Vec<WeatherEntry>,
+}
+
+/// Outcome of a forecast fetch
+#[derive(Message)]
+#[rtype(result = "()")]
+pub enum WeatherUpdate {
+    Fetched {
+        forecast: Forecast,
+        /// Unix timestamp of the fetch
+        fetched_at: i64,
+    },
+    Failed {
+        error: String,
+    },
 }
 
 impl WeatherData {
-    pub fn zero() -> Self {
-        Self {
-            entries: Default::default(),
-        }
+    /// Select the entries of `slots` relative to `now`. `alternate` is toggled on every render
+    /// and decides which temperature the slots showing both of them show.
+    pub fn at(
+        forecast: &Forecast,
+        slots: &[SlotSpec],
+        now: OffsetDateTime,
+        tz: &LocalTimeZone,
+        alternate: bool,
+    ) -> Self {
+        let entries = slots
+            .iter()
+            .map(|spec| {
+                let feels_like = spec.kind.shows_feels_like(alternate);
+                let value = spec.slot.select(forecast, now, tz, feels_like);
+                WeatherEntry {
+                    temperature: value.map(|(t, _)| t),
+                    conditions: value.map(|(_, rs)| rs).unwrap_or_default(),
+                    label: spec.label.clone(),
+                }
+            })
+            .collect();
+        Self { entries }
     }
 }
 
 pub struct Weather {
-    subscriber: Recipient<WeatherData>,
-    owm_url: Arc<String>,
+    subscriber: Recipient<WeatherUpdate>,
+    provider: Rc<dyn WeatherProvider>,
+    cache: ForecastCache,
 }
 
+/// Shortest wait before fetching again after a failure
+const MIN_RETRY: Duration = Duration::from_secs(10);
+
 #[derive(Default, Message)]
 #[rtype(result = "()")]
 struct Tick;
 
 impl Weather {
     pub fn with_subscriber(
-        subscriber: Recipient<WeatherData>,
-        latitude: String,
-        longitude: String,
-        units: String,
-        appid: String,
+        subscriber: Recipient<WeatherUpdate>,
+        provider: Box<dyn WeatherProvider>,
+        cache: ForecastCache,
     ) -> Self {
-        let owm_url = format!("https://api.openweathermap.org/data/3.0/onecall?units={units}&lat={latitude}&lon={longitude}&appid={appid}");
         Self {
             subscriber,
-            owm_url: Arc::new(owm_url),
+            provider: provider.into(),
+            cache,
         }
     }
 }
@@ -127,6 +275,17 @@ impl Actor for Weather {
     type Context = Context<Self>;
 
     fn started(&mut self, ctx: &mut Self::Context) {
+        // show the last known forecast until the first fetch succeeds
+        match self.cache.load() {
+            Ok(cached) => {
+                self.subscriber.do_send(WeatherUpdate::Fetched {
+                    forecast: cached.forecast,
+                    fetched_at: cached.fetched_at,
+                });
+            }
+            Err(e) => eprintln!("no cached forecast: {e:#}"),
+        }
+
         ctx.notify(Tick);
     }
 }
@@ -137,21 +296,49 @@ impl Handler<Tick> for Weather {
     fn handle(&mut self, _: Tick, ctx: &mut Self::Context) {
         let sub = self.subscriber.clone();
         let ticker = ctx.address();
-        let owm_url = Arc::clone(&self.owm_url);
+        let provider = Rc::clone(&self.provider);
+        let cache = self.cache.clone();
 
         async move {
             let sub = sub;
 
-            let w = crate::owm::fetch_current_weather(&owm_url).await;
+            let w = provider.fetch().await;
             match w {
                 Ok(w) => {
-                    let _ = sub.send(w.into()).await;
+                    let now = OffsetDateTime::now_utc().unix_timestamp();
+                    let cached = CachedForecast {
+                        fetched_at: now,
+                        forecast: w.clone(),
+                    };
+                    if let Err(e) = cache.store(&cached) {
+                        eprintln!("failed to cache the forecast: {e:#}");
+                    }
+                    let _ = sub
+                        .send(WeatherUpdate::Fetched {
+                            forecast: w,
+                            fetched_at: now,
+                        })
+                        .await;
                     tokio::time::sleep(Duration::from_secs(3600)).await;
                     ticker.send(Tick).await.unwrap();
                 }
                 Err(e) => {
-                    dbg!(e);
-                    tokio::time::sleep(Duration::from_secs(10)).await;
+                    let _ = sub
+                        .send(WeatherUpdate::Failed {
+                            error: format!("{e:#}"),
+                        })
+                        .await;
+                    // the providers are skipped while cooling down, so wait until one of them
+                    // can be tried again
+                    let retry_at = provider
+                        .ready_at()
+                        .unwrap_or_else(|| Instant::now() + MIN_RETRY)
+                        .max(Instant::now() + MIN_RETRY);
+                    eprintln!(
+                        "no forecast, retrying in {}s: {e:#}",
+                        retry_at.saturating_duration_since(Instant::now()).as_secs()
+                    );
+                    tokio::time::sleep_until(retry_at.into()).await;
                     ticker.send(Tick).await.unwrap();
                 }
             };
@@ -0,0 +1,30 @@
+/// The 16 points of the compass, clockwise from north
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// Upper bounds of the Beaufort wind forces 0 to 11 in m/s, stronger winds are force 12
+const BEAUFORT_LIMITS: [f32; 12] = [
+    0.5, 1.6, 3.4, 5.5, 8.0, 10.8, 13.9, 17.2, 20.8, 24.5, 28.5, 32.7,
+];
+
+/// Closest point of the compass to `degrees` clockwise from north
+pub fn compass_point(degrees: f32) -> &'static str {
+    let index = (degrees.rem_euclid(360.0) / 22.5).round() as usize;
+    COMPASS_POINTS[index % COMPASS_POINTS.len()]
+}
+
+/// Degrees clockwise from north of a point of the compass, such as NW
+pub fn compass_degrees(point: &str) -> Option<f32> {
+    let index = COMPASS_POINTS.iter().position(|p| *p == point)?;
+    Some(index as f32 * 22.5)
+}
+
+/// Wind force on the Beaufort scale of a wind blowing at `speed` m/s
+pub fn beaufort(speed: f32) -> u8 {
+    BEAUFORT_LIMITS
+        .iter()
+        .take_while(|limit| speed >= **limit)
+        .count() as u8
+}